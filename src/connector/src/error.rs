@@ -0,0 +1,262 @@
+// Copyright 2023 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Structured, accumulating error reporting for connector config deserialization.
+//!
+//! `serde`'s `Deserialize` aborts at the first bad field, so a config with three typos forces a
+//! user through three edit-retry cycles. [`ConfigDiagnostics`] collects one [`ConfigDiagnostic`]
+//! per bad field instead, so `parser`/`source`/`sink` config validation can report everything
+//! wrong with a config map in one pass.
+
+use std::fmt;
+
+/// A secondary location attached to a [`ConfigDiagnostic`] that helps explain the primary
+/// problem without being one itself, e.g. `("s3.region", "required because connector=s3")`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RelatedNote {
+    pub field: String,
+    pub message: String,
+}
+
+/// One problem found while validating a config map: the dotted field path it occurred at, the
+/// offending raw value (if the field was present at all), the expected domain (e.g. the
+/// "true or false" / duration-unit messages already passed to `serde`'s `invalid_value`), and any
+/// related secondary locations.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConfigDiagnostic {
+    pub field: String,
+    pub offending_value: Option<String>,
+    pub expected: String,
+    pub related: Vec<RelatedNote>,
+}
+
+impl ConfigDiagnostic {
+    pub fn new(field: impl Into<String>, expected: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            offending_value: None,
+            expected: expected.into(),
+            related: Vec::new(),
+        }
+    }
+
+    pub fn with_value(mut self, offending_value: impl Into<String>) -> Self {
+        self.offending_value = Some(offending_value.into());
+        self
+    }
+
+    pub fn with_related(mut self, field: impl Into<String>, message: impl Into<String>) -> Self {
+        self.related.push(RelatedNote {
+            field: field.into(),
+            message: message.into(),
+        });
+        self
+    }
+
+    /// Renders this diagnostic as one or more standalone diagnostics with no related notes: the
+    /// primary one, followed by one per related note (field set to the note's own field, expected
+    /// set to the note's message). For consumers that can only display a flat list of
+    /// `field: expected` problems, e.g. a single-line CLI error.
+    pub fn flatten(&self) -> Vec<ConfigDiagnostic> {
+        let mut flattened = Vec::with_capacity(1 + self.related.len());
+        flattened.push(ConfigDiagnostic {
+            field: self.field.clone(),
+            offending_value: self.offending_value.clone(),
+            expected: self.expected.clone(),
+            related: Vec::new(),
+        });
+        flattened.extend(
+            self.related
+                .iter()
+                .map(|note| ConfigDiagnostic::new(note.field.clone(), note.message.clone())),
+        );
+        flattened
+    }
+}
+
+impl fmt::Display for ConfigDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.offending_value {
+            Some(value) => write!(
+                f,
+                "field `{}`: invalid value `{}`, expected {}",
+                self.field, value, self.expected
+            )?,
+            None => write!(f, "field `{}`: expected {}", self.field, self.expected)?,
+        }
+        for note in &self.related {
+            write!(f, "\n  related: `{}`: {}", note.field, note.message)?;
+        }
+        Ok(())
+    }
+}
+
+/// Accumulates [`ConfigDiagnostic`]s across many independent field checks instead of
+/// short-circuiting on the first one, the way `serde`'s `Deserialize` does. A connector's config
+/// validation calls [`ConfigDiagnostics::check`] once per field (backed by the same pure
+/// `parse_*_property` helpers the `deserialize_with` functions in `crate::lib` use) and only
+/// converts the result to a `Result` once every field has been checked, via
+/// [`ConfigDiagnostics::into_result`].
+#[derive(Debug, Default)]
+pub struct ConfigDiagnostics {
+    diagnostics: Vec<ConfigDiagnostic>,
+}
+
+impl ConfigDiagnostics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, diagnostic: ConfigDiagnostic) {
+        self.diagnostics.push(diagnostic);
+    }
+
+    /// Runs `parse` against `raw` and records a [`ConfigDiagnostic`] under `field` if it fails,
+    /// without stopping. `parse` is expected to return `Err(expected_domain_message)` on failure,
+    /// the same shape as `crate::parse_bool_property` / `crate::parse_duration_property`. Returns
+    /// `None` both when `raw` is absent and when it failed to parse; the absent case records no
+    /// diagnostic here since "field missing" and "field invalid" are reported separately by the
+    /// caller (e.g. via [`ConfigDiagnostics::require`]).
+    pub fn check<T>(
+        &mut self,
+        field: &str,
+        raw: Option<&str>,
+        parse: impl FnOnce(&str) -> Result<T, &'static str>,
+    ) -> Option<T> {
+        let raw = raw?;
+        match parse(raw) {
+            Ok(value) => Some(value),
+            Err(expected) => {
+                self.push(ConfigDiagnostic::new(field, expected).with_value(raw));
+                None
+            }
+        }
+    }
+
+    /// Like [`ConfigDiagnostics::check`], but also records a diagnostic when `raw` is absent.
+    pub fn require<T>(
+        &mut self,
+        field: &str,
+        raw: Option<&str>,
+        parse: impl FnOnce(&str) -> Result<T, &'static str>,
+    ) -> Option<T> {
+        if raw.is_none() {
+            self.push(ConfigDiagnostic::new(
+                field,
+                "a value, but the field was not set",
+            ));
+        }
+        self.check(field, raw, parse)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    /// `Ok(())` if nothing was recorded, else every diagnostic collected so far, in the order
+    /// fields were checked.
+    pub fn into_result(self) -> Result<(), Vec<ConfigDiagnostic>> {
+        if self.diagnostics.is_empty() {
+            Ok(())
+        } else {
+            Err(self.diagnostics)
+        }
+    }
+
+    /// Every diagnostic collected so far, with related notes expanded into their own standalone
+    /// entries. See [`ConfigDiagnostic::flatten`].
+    pub fn flattened(&self) -> Vec<ConfigDiagnostic> {
+        self.diagnostics
+            .iter()
+            .flat_map(ConfigDiagnostic::flatten)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_bool_property, parse_duration_property};
+
+    #[test]
+    fn test_check_collects_multiple_failures_without_short_circuiting() {
+        let mut diagnostics = ConfigDiagnostics::new();
+        diagnostics.check("connector.enabled", Some("maybe"), parse_bool_property);
+        diagnostics.check(
+            "connector.interval",
+            Some("3 fortnights"),
+            parse_duration_property,
+        );
+        diagnostics.check("connector.retries", Some("5"), |s| {
+            s.parse::<u32>().map_err(|_| "an integer")
+        });
+
+        let errors = diagnostics.into_result().unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].field, "connector.enabled");
+        assert_eq!(errors[0].offending_value.as_deref(), Some("maybe"));
+        assert_eq!(errors[1].field, "connector.interval");
+    }
+
+    #[test]
+    fn test_check_succeeds_without_recording_a_diagnostic() {
+        let mut diagnostics = ConfigDiagnostics::new();
+        let enabled = diagnostics.check("connector.enabled", Some("true"), parse_bool_property);
+        assert_eq!(enabled, Some(true));
+        assert!(diagnostics.is_empty());
+        assert_eq!(diagnostics.into_result(), Ok(()));
+    }
+
+    #[test]
+    fn test_require_reports_a_missing_field() {
+        let mut diagnostics = ConfigDiagnostics::new();
+        let region = diagnostics.require("s3.region", None, parse_bool_property);
+        assert_eq!(region, None);
+
+        let errors = diagnostics.into_result().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field, "s3.region");
+        assert_eq!(errors[0].offending_value, None);
+    }
+
+    #[test]
+    fn test_flatten_emits_related_notes_as_standalone_diagnostics() {
+        let diagnostic = ConfigDiagnostic::new("connector", "one of: s3, kafka, pulsar")
+            .with_value("gcs")
+            .with_related("s3.region", "required because connector=s3")
+            .with_related("s3.bucket", "required because connector=s3");
+
+        let flattened = diagnostic.flatten();
+        assert_eq!(flattened.len(), 3);
+        assert_eq!(flattened[0].field, "connector");
+        assert!(flattened[0].related.is_empty());
+        assert_eq!(flattened[1].field, "s3.region");
+        assert_eq!(flattened[1].expected, "required because connector=s3");
+        assert_eq!(flattened[2].field, "s3.bucket");
+    }
+
+    #[test]
+    fn test_config_diagnostics_flattened_expands_every_entry() {
+        let mut diagnostics = ConfigDiagnostics::new();
+        diagnostics.push(
+            ConfigDiagnostic::new("connector", "one of: s3, kafka, pulsar")
+                .with_value("gcs")
+                .with_related("s3.region", "required because connector=s3"),
+        );
+        diagnostics
+            .push(ConfigDiagnostic::new("connector.enabled", "true or false").with_value("maybe"));
+
+        assert_eq!(diagnostics.flattened().len(), 3);
+    }
+}
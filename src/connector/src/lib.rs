@@ -27,8 +27,11 @@
 #![feature(box_into_inner)]
 #![feature(type_alias_impl_trait)]
 
+use std::borrow::Cow;
 use std::time::Duration;
 
+use anyhow::anyhow;
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
 use duration_str::parse_std;
 use serde::de;
 
@@ -42,17 +45,51 @@ pub mod source;
 
 pub mod common;
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 pub struct ConnectorParams {
     pub connector_rpc_endpoint: Option<String>,
+    /// Timezone a source/sink's start-offset and windowing properties are resolved against when
+    /// their value is a bare date or a relative duration rather than a fully qualified timestamp.
+    /// See [`parse_start_offset`].
+    pub timezone: FixedOffset,
+}
+
+impl Default for ConnectorParams {
+    fn default() -> Self {
+        Self {
+            connector_rpc_endpoint: None,
+            timezone: FixedOffset::east_opt(0).unwrap(),
+        }
+    }
 }
 
 impl ConnectorParams {
     pub fn new(connector_rpc_endpoint: Option<String>) -> Self {
         Self {
             connector_rpc_endpoint,
+            timezone: FixedOffset::east_opt(0).unwrap(),
         }
     }
+
+    pub fn with_timezone(mut self, timezone: FixedOffset) -> Self {
+        self.timezone = timezone;
+        self
+    }
+}
+
+/// Human-readable description of the domain `parse_bool_property` accepts, shared between the
+/// `serde` error path and [`crate::error::ConfigDiagnostics`]'s accumulating one so the two never
+/// drift apart.
+const BOOL_PROPERTY_EXPECTED: &str = "true or false";
+
+/// Core of [`deserialize_bool_from_string`], pulled out so [`crate::error::ConfigDiagnostics`]
+/// can validate this field without going through `serde`'s short-circuiting `Deserialize`.
+pub(crate) fn parse_bool_property(s: &str) -> Result<bool, &'static str> {
+    match s.to_ascii_lowercase().as_str() {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        _ => Err(BOOL_PROPERTY_EXPECTED),
+    }
 }
 
 pub(crate) fn deserialize_bool_from_string<'de, D>(deserializer: D) -> Result<bool, D::Error>
@@ -60,15 +97,19 @@ where
     D: de::Deserializer<'de>,
 {
     let s: String = de::Deserialize::deserialize(deserializer)?;
-    let s = s.to_ascii_lowercase();
-    match s.as_str() {
-        "true" => Ok(true),
-        "false" => Ok(false),
-        _ => Err(de::Error::invalid_value(
-            de::Unexpected::Str(&s),
-            &"true or false",
-        )),
-    }
+    parse_bool_property(&s)
+        .map_err(|_| de::Error::invalid_value(de::Unexpected::Str(&s), &BOOL_PROPERTY_EXPECTED))
+}
+
+/// Human-readable description of the domain `parse_duration_property` accepts, shared between the
+/// `serde` error path and [`crate::error::ConfigDiagnostics`]'s accumulating one.
+const DURATION_PROPERTY_EXPECTED: &str =
+    "The String value unit support for one of:[“y”,“mon”,“w”,“d”,“h”,“m”,“s”, “ms”, “µs”, “ns”]";
+
+/// Core of [`deserialize_duration_from_string`], pulled out so [`crate::error::ConfigDiagnostics`]
+/// can validate this field without going through `serde`'s short-circuiting `Deserialize`.
+pub(crate) fn parse_duration_property(s: &str) -> Result<Duration, &'static str> {
+    parse_std(s).map_err(|_| DURATION_PROPERTY_EXPECTED)
 }
 
 pub(crate) fn deserialize_duration_from_string<'de, D>(
@@ -78,8 +119,279 @@ where
     D: de::Deserializer<'de>,
 {
     let s: String = de::Deserialize::deserialize(deserializer)?;
-    parse_std(&s).map_err(|_| de::Error::invalid_value(
-        de::Unexpected::Str(&s),
-        &"The String value unit support for one of:[“y”,“mon”,“w”,“d”,“h”,“m”,“s”, “ms”, “µs”, “ns”]",
-    ))
+    parse_duration_property(&s)
+        .map_err(|_| de::Error::invalid_value(de::Unexpected::Str(&s), &DURATION_PROPERTY_EXPECTED))
+}
+
+/// Context needed to interpret a source/sink scan start-offset or windowing property whose value
+/// isn't a fully qualified timestamp: the timezone a bare date or relative duration is resolved
+/// against, and an optional fixed "now" (completing a bare date to midnight, or serving as the
+/// anchor a relative duration is subtracted from) so parsing is deterministic in tests instead of
+/// reading the system clock.
+#[derive(Clone, Copy, Debug)]
+pub struct StartOffsetParseContext {
+    pub timezone: FixedOffset,
+    pub override_date: Option<NaiveDate>,
+}
+
+impl StartOffsetParseContext {
+    pub fn new(timezone: FixedOffset) -> Self {
+        Self {
+            timezone,
+            override_date: None,
+        }
+    }
+
+    fn now(&self) -> DateTime<FixedOffset> {
+        match self.override_date {
+            Some(date) => date
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+                .and_local_timezone(self.timezone)
+                .unwrap(),
+            None => Utc::now().with_timezone(&self.timezone),
+        }
+    }
+}
+
+/// Parses a source/sink start-offset or windowing property, accepting either an absolute
+/// timestamp or a relative duration, and normalizing either to a single `DateTime<Utc>` so the
+/// rest of the connector works against one clock regardless of which form the user wrote:
+///
+/// - An RFC3339 timestamp (e.g. `2023-06-01T00:00:00+02:00`) is used as-is.
+/// - A bare date (`2023-06-01`) is completed with midnight in `ctx.timezone`.
+/// - Anything else falls back to [`deserialize_duration_from_string`]'s relative-duration syntax
+///   (`2h`, `30m`, ...) and is subtracted from `ctx`'s "now".
+pub fn parse_start_offset(
+    raw: &str,
+    ctx: &StartOffsetParseContext,
+) -> Result<DateTime<Utc>, anyhow::Error> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(raw) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+
+    if let Ok(date) = NaiveDate::parse_from_str(raw, "%Y-%m-%d") {
+        let local = date
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_local_timezone(ctx.timezone)
+            .single()
+            .ok_or_else(|| {
+                anyhow!("ambiguous local midnight for {raw:?} in the configured timezone")
+            })?;
+        return Ok(local.with_timezone(&Utc));
+    }
+
+    let duration = parse_std(raw).map_err(|_| {
+        anyhow!(
+            "expected an RFC3339 timestamp, a bare date (YYYY-MM-DD), or a relative duration \
+             like \"2h\", got {raw:?}"
+        )
+    })?;
+    let duration = chrono::Duration::from_std(duration)
+        .map_err(|e| anyhow!("duration {raw:?} out of range: {e}"))?;
+    Ok(ctx.now().with_timezone(&Utc) - duration)
+}
+
+/// Parses a fixed UTC offset written the way `properties.timezone` config fields accept it:
+/// `"Z"`/`"UTC"` for zero offset, or a signed `"+HH:MM"`/`"-HH:MM"` otherwise. This is the
+/// timezone [`parse_start_offset`] resolves a bare date or relative duration against, so a
+/// source/sink whose scan start-offset needs to honor a configured (rather than hardcoded UTC)
+/// timezone exposes a `properties.timezone` field deserialized with
+/// [`deserialize_fixed_offset_from_string`] and builds its [`StartOffsetParseContext`] from it --
+/// see `KafkaConfig::timezone`/`KafkaConfig::recovery_scan_start_timestamp` in `sink/kafka.rs`.
+pub(crate) fn parse_fixed_offset(s: &str) -> Result<FixedOffset, anyhow::Error> {
+    if s.eq_ignore_ascii_case("z") || s.eq_ignore_ascii_case("utc") {
+        return Ok(FixedOffset::east_opt(0).unwrap());
+    }
+    let (sign, rest) = match s.as_bytes().first() {
+        Some(b'+') => (1, &s[1..]),
+        Some(b'-') => (-1, &s[1..]),
+        _ => {
+            return Err(anyhow!(
+                "timezone {s:?} must be \"Z\", \"UTC\", or of the form \"+HH:MM\"/\"-HH:MM\""
+            ))
+        }
+    };
+    let (hh, mm) = rest
+        .split_once(':')
+        .ok_or_else(|| anyhow!("timezone {s:?} must be of the form \"+HH:MM\"/\"-HH:MM\""))?;
+    let hh: i32 = hh
+        .parse()
+        .map_err(|_| anyhow!("invalid timezone hour in {s:?}"))?;
+    let mm: i32 = mm
+        .parse()
+        .map_err(|_| anyhow!("invalid timezone minute in {s:?}"))?;
+    let seconds = sign * (hh * 3600 + mm * 60);
+    FixedOffset::east_opt(seconds).ok_or_else(|| anyhow!("timezone offset {s:?} out of range"))
+}
+
+pub(crate) fn deserialize_fixed_offset_from_string<'de, D>(
+    deserializer: D,
+) -> Result<FixedOffset, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    let s: String = de::Deserialize::deserialize(deserializer)?;
+    parse_fixed_offset(&s).map_err(de::Error::custom)
+}
+
+pub(crate) fn default_utc_offset() -> FixedOffset {
+    FixedOffset::east_opt(0).unwrap()
+}
+
+/// Deserializes a string property the same way [`deserialize_bool_from_string`] and
+/// [`deserialize_duration_from_string`] deserialize theirs, except it never fails on malformed
+/// unicode: connector property values frequently pass through external JSON a second time (Kafka
+/// headers, Debezium envelopes, CDC payloads re-embedding a JSON string inside another), and what
+/// reaches us can still contain literal `\uXXXX` escape text left over from that outer encoding.
+/// Any such escape that doesn't form a valid UTF-16 code point (a lone high or low surrogate) is
+/// replaced with U+FFFD instead of failing the whole deserialization. Usable as a
+/// `#[serde(deserialize_with = "deserialize_lossy_string")]` on a `String` field in the config
+/// structs `parser`/`source`/`sink` consume.
+pub(crate) fn deserialize_lossy_string<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    let s: String = de::Deserialize::deserialize(deserializer)?;
+    Ok(lossy_unescape(&s).into_owned())
+}
+
+/// Walks `s` for `\uXXXX` escape sequences, decoding runs of them as UTF-16 code units so
+/// surrogate pairs recombine into one code point, same as a conforming JSON decoder would -- but
+/// substituting U+FFFD for any lone surrogate instead of erroring. Falls straight through to a
+/// borrowed, zero-allocation view of `s` when there's nothing to unescape.
+fn lossy_unescape(s: &str) -> Cow<'_, str> {
+    if !s.contains("\\u") {
+        return Cow::Borrowed(s);
+    }
+
+    fn flush(units: &mut Vec<u16>, out: &mut String) {
+        for decoded in char::decode_utf16(units.drain(..)) {
+            out.push(decoded.unwrap_or(char::REPLACEMENT_CHARACTER));
+        }
+    }
+
+    let bytes = s.as_bytes();
+    let mut out = String::with_capacity(s.len());
+    let mut units: Vec<u16> = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'\\' && i + 6 <= bytes.len() && bytes[i + 1] == b'u' {
+            if let Ok(unit) = u16::from_str_radix(&s[i + 2..i + 6], 16) {
+                units.push(unit);
+                i += 6;
+                continue;
+            }
+        }
+        flush(&mut units, &mut out);
+        // `i` is always on a char boundary: we only ever advance it by a whole (ASCII) escape
+        // sequence above or by a whole char below.
+        let ch = s[i..].chars().next().unwrap();
+        out.push(ch);
+        i += ch.len_utf8();
+    }
+    flush(&mut units, &mut out);
+    Cow::Owned(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lossy_unescape_passes_through_plain_text() {
+        assert_eq!(lossy_unescape("hello world"), Cow::Borrowed("hello world"));
+    }
+
+    #[test]
+    fn test_lossy_unescape_decodes_valid_surrogate_pair() {
+        // U+D83D U+DE00 is the UTF-16 surrogate pair for 😀 (U+1F600).
+        assert_eq!(lossy_unescape("say \\uD83D\\uDE00 hi"), "say \u{1F600} hi");
+    }
+
+    #[test]
+    fn test_lossy_unescape_replaces_lone_high_surrogate() {
+        assert_eq!(
+            lossy_unescape(r"broken \uD800 escape"),
+            format!("broken {} escape", char::REPLACEMENT_CHARACTER)
+        );
+    }
+
+    #[test]
+    fn test_lossy_unescape_replaces_lone_low_surrogate() {
+        assert_eq!(
+            lossy_unescape(r"broken \uDC00 escape"),
+            format!("broken {} escape", char::REPLACEMENT_CHARACTER)
+        );
+    }
+
+    #[test]
+    fn test_deserialize_lossy_string_recovers_from_lone_surrogate() {
+        let value = serde_json::Value::String(r"broken \uD800 escape".to_owned());
+        let decoded = deserialize_lossy_string(value).unwrap();
+        assert_eq!(
+            decoded,
+            format!("broken {} escape", char::REPLACEMENT_CHARACTER)
+        );
+    }
+
+    #[test]
+    fn test_parse_start_offset_rfc3339() {
+        let ctx = StartOffsetParseContext::new(FixedOffset::east_opt(0).unwrap());
+        let parsed = parse_start_offset("2023-06-01T00:00:00+02:00", &ctx).unwrap();
+        assert_eq!(parsed.to_rfc3339(), "2023-05-31T22:00:00+00:00");
+    }
+
+    #[test]
+    fn test_parse_start_offset_bare_date_uses_configured_timezone() {
+        let ctx = StartOffsetParseContext::new(FixedOffset::east_opt(2 * 3600).unwrap());
+        let parsed = parse_start_offset("2023-06-01", &ctx).unwrap();
+        // Midnight in UTC+2 is 22:00 UTC the previous day.
+        assert_eq!(parsed.to_rfc3339(), "2023-05-31T22:00:00+00:00");
+    }
+
+    #[test]
+    fn test_parse_start_offset_relative_duration_uses_override_now() {
+        let mut ctx = StartOffsetParseContext::new(FixedOffset::east_opt(0).unwrap());
+        ctx.override_date = NaiveDate::from_ymd_opt(2023, 6, 1);
+        let parsed = parse_start_offset("2h", &ctx).unwrap();
+        assert_eq!(parsed.to_rfc3339(), "2023-05-31T22:00:00+00:00");
+    }
+
+    #[test]
+    fn test_parse_start_offset_rejects_garbage() {
+        let ctx = StartOffsetParseContext::new(FixedOffset::east_opt(0).unwrap());
+        assert!(parse_start_offset("not a timestamp", &ctx).is_err());
+    }
+
+    #[test]
+    fn test_parse_fixed_offset_utc_aliases() {
+        assert_eq!(
+            parse_fixed_offset("Z").unwrap(),
+            FixedOffset::east_opt(0).unwrap()
+        );
+        assert_eq!(
+            parse_fixed_offset("UTC").unwrap(),
+            FixedOffset::east_opt(0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_fixed_offset_positive_and_negative() {
+        assert_eq!(
+            parse_fixed_offset("+08:00").unwrap(),
+            FixedOffset::east_opt(8 * 3600).unwrap()
+        );
+        assert_eq!(
+            parse_fixed_offset("-05:30").unwrap(),
+            FixedOffset::east_opt(-(5 * 3600 + 30 * 60)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_fixed_offset_rejects_garbage() {
+        assert!(parse_fixed_offset("not a timezone").is_err());
+        assert!(parse_fixed_offset("+08").is_err());
+    }
 }
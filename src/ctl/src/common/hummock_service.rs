@@ -13,10 +13,15 @@
 // limitations under the License.
 
 use std::env;
+use std::fs;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::{anyhow, bail, Result};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, Server};
+use prometheus::{Encoder, Registry, TextEncoder};
 use risingwave_rpc_client::MetaClient;
 use risingwave_storage::hummock::hummock_meta_client::MonitoredHummockMetaClient;
 use risingwave_storage::hummock::{HummockStorage, TieredCacheMetricsBuilder};
@@ -31,6 +36,10 @@ use tokio::task::JoinHandle;
 
 pub struct HummockServiceOpts {
     pub hummock_url: String,
+    /// When set, `create_hummock_store_with_metrics` registers real metrics against a
+    /// fresh [`Registry`] and serves them over `GET /metrics` on this address, instead of
+    /// the default `::unused()` metrics.
+    metrics_listen_addr: Option<SocketAddr>,
 
     heartbeat_handle: Option<JoinHandle<()>>,
     heartbeat_shutdown_sender: Option<Sender<()>>,
@@ -38,6 +47,7 @@ pub struct HummockServiceOpts {
 
 #[derive(Clone)]
 pub struct Metrics {
+    pub registry: Registry,
     pub hummock_metrics: Arc<HummockMetrics>,
     pub state_store_metrics: Arc<HummockStateStoreMetrics>,
     pub object_store_metrics: Arc<ObjectStoreMetrics>,
@@ -45,19 +55,71 @@ pub struct Metrics {
     pub compactor_metrics: Arc<CompactorMetrics>,
 }
 
+/// Serve `registry` as Prometheus text format on `GET /metrics` at `listen_addr` until
+/// the process exits.
+fn start_metrics_server(registry: Registry, listen_addr: SocketAddr) -> JoinHandle<()> {
+    let make_svc = make_service_fn(move |_| {
+        let registry = registry.clone();
+        async move {
+            Ok::<_, hyper::Error>(service_fn(move |req: Request<Body>| {
+                let registry = registry.clone();
+                async move {
+                    if req.uri().path() != "/metrics" {
+                        return Ok::<_, hyper::Error>(
+                            Response::builder()
+                                .status(404)
+                                .body(Body::from("not found"))
+                                .unwrap(),
+                        );
+                    }
+                    let mut buffer = vec![];
+                    let encoder = TextEncoder::new();
+                    encoder
+                        .encode(&registry.gather(), &mut buffer)
+                        .expect("failed to encode metrics");
+                    Ok(Response::new(Body::from(buffer)))
+                }
+            }))
+        }
+    });
+
+    tokio::spawn(async move {
+        tracing::info!(
+            "risectl metrics listening on http://{}/metrics",
+            listen_addr
+        );
+        if let Err(e) = Server::bind(&listen_addr).serve(make_svc).await {
+            tracing::warn!("risectl metrics server exited: {}", e);
+        }
+    })
+}
+
 impl HummockServiceOpts {
     /// Recover hummock service options from env variable
     ///
     /// Currently, we will read these variables for meta:
     ///
     /// * `RW_HUMMOCK_URL`: hummock store address
+    /// * `RW_HUMMOCK_URL_FILE`: path to a file containing the hummock store address, for
+    ///   setups (e.g. k8s secrets) that mount credentials as files instead of env vars
     pub fn from_env() -> Result<Self> {
-        let hummock_url = match env::var("RW_HUMMOCK_URL") {
-            Ok(url) => {
+        let hummock_url = match (env::var("RW_HUMMOCK_URL"), env::var("RW_HUMMOCK_URL_FILE")) {
+            (Ok(_), Ok(_)) => {
+                bail!("only one of `RW_HUMMOCK_URL` / `RW_HUMMOCK_URL_FILE` may be set");
+            }
+            (Ok(url), Err(_)) => {
                 tracing::info!("using Hummock URL from `RW_HUMMOCK_URL`: {}", url);
                 url
             }
-            Err(_) => {
+            (Err(_), Ok(path)) => {
+                let url = fs::read_to_string(&path)
+                    .map_err(|e| anyhow!("failed to read `RW_HUMMOCK_URL_FILE` `{}`: {}", path, e))?
+                    .trim_end()
+                    .to_owned();
+                tracing::info!("using Hummock URL from `RW_HUMMOCK_URL_FILE`: {}", path);
+                url
+            }
+            (Err(_), Err(_)) => {
                 const MESSAGE: &str = "env variable `RW_HUMMOCK_URL` not found.
 
 For `./risedev d` use cases, please do the following.
@@ -74,11 +136,75 @@ For `./risedev apply-compose-deploy` users,
         };
         Ok(Self {
             hummock_url,
+            metrics_listen_addr: None,
             heartbeat_handle: None,
             heartbeat_shutdown_sender: None,
         })
     }
 
+    /// Opt into real Hummock/object-store metrics, served over `GET /metrics` on
+    /// `listen_addr`, instead of the default `::unused()` metrics.
+    pub fn with_metrics(mut self, listen_addr: SocketAddr) -> Self {
+        self.metrics_listen_addr = Some(listen_addr);
+        self
+    }
+
+    /// Build the [`StorageOpts`] risectl will use to open Hummock.
+    ///
+    /// By default this matches today's hardcoded tuning (single-threaded shared buffer
+    /// compaction). If `RW_STORAGE_CONFIG` points at a TOML file, its contents are
+    /// deserialized on top of those defaults, so operators can override block cache size,
+    /// compaction worker threads, object-store timeouts, etc. without recompiling
+    /// `risectl`. A missing env variable falls back to the defaults; a present but
+    /// unparsable file is a hard error.
+    fn load_storage_opts_from_env() -> Result<StorageOpts> {
+        let default_opts = StorageOpts {
+            share_buffer_compaction_worker_threads_number: 0,
+            ..Default::default()
+        };
+
+        let config_path = match env::var("RW_STORAGE_CONFIG") {
+            Ok(path) => path,
+            Err(_) => return Ok(default_opts),
+        };
+
+        tracing::info!(
+            "loading StorageOpts from `RW_STORAGE_CONFIG`: {}",
+            config_path
+        );
+
+        let config_str = fs::read_to_string(&config_path).map_err(|e| {
+            anyhow!(
+                "failed to read storage config file `{}`: {}",
+                config_path,
+                e
+            )
+        })?;
+        let overrides: toml::Value = toml::from_str(&config_str).map_err(|e| {
+            anyhow!(
+                "failed to parse storage config file `{}` as TOML: {}",
+                config_path,
+                e
+            )
+        })?;
+
+        let mut merged = toml::Value::try_from(&default_opts)
+            .expect("StorageOpts must always serialize to a TOML table");
+        if let (Some(merged_table), toml::Value::Table(override_table)) =
+            (merged.as_table_mut(), overrides)
+        {
+            merged_table.extend(override_table);
+        }
+
+        merged.try_into().map_err(|e| {
+            anyhow!(
+                "storage config file `{}` does not match StorageOpts: {}",
+                config_path,
+                e
+            )
+        })
+    }
+
     pub async fn create_hummock_store_with_metrics(
         &mut self,
         meta_client: &MetaClient,
@@ -92,20 +218,41 @@ For `./risedev apply-compose-deploy` users,
         self.heartbeat_handle = Some(heartbeat_handle);
         self.heartbeat_shutdown_sender = Some(heartbeat_shutdown_sender);
 
-        // FIXME: allow specify custom config
-        let opts = StorageOpts {
-            share_buffer_compaction_worker_threads_number: 0,
-            ..Default::default()
-        };
+        let opts = Self::load_storage_opts_from_env()?;
 
         tracing::info!("using StorageOpts: {:#?}", opts);
 
-        let metrics = Metrics {
-            hummock_metrics: Arc::new(HummockMetrics::unused()),
-            state_store_metrics: Arc::new(HummockStateStoreMetrics::unused()),
-            object_store_metrics: Arc::new(ObjectStoreMetrics::unused()),
-            storage_metrics: Arc::new(MonitoredStorageMetrics::unused()),
-            compactor_metrics: Arc::new(CompactorMetrics::unused()),
+        let metrics = match self.metrics_listen_addr {
+            Some(listen_addr) => {
+                let registry = Registry::new();
+                // `ObjectStoreMetrics`/`HummockMetrics`/etc. below already register real,
+                // continuously-updated counters (including object-store read bytes and
+                // per-compression-algorithm block counts) against `registry`; risectl has no
+                // object-store call site of its own to instrument, so serving their registry
+                // over `/metrics` is sufficient without inventing parallel risectl-only gauges
+                // that nothing in this binary would ever increment.
+                let metrics = Metrics {
+                    hummock_metrics: Arc::new(HummockMetrics::new(registry.clone())),
+                    state_store_metrics: Arc::new(HummockStateStoreMetrics::new(registry.clone())),
+                    object_store_metrics: Arc::new(ObjectStoreMetrics::new(registry.clone())),
+                    storage_metrics: Arc::new(MonitoredStorageMetrics::new(registry.clone())),
+                    compactor_metrics: Arc::new(CompactorMetrics::new(registry.clone())),
+                    registry,
+                };
+                start_metrics_server(metrics.registry.clone(), listen_addr);
+                metrics
+            }
+            None => {
+                let registry = Registry::new();
+                Metrics {
+                    hummock_metrics: Arc::new(HummockMetrics::unused()),
+                    state_store_metrics: Arc::new(HummockStateStoreMetrics::unused()),
+                    object_store_metrics: Arc::new(ObjectStoreMetrics::unused()),
+                    storage_metrics: Arc::new(MonitoredStorageMetrics::unused()),
+                    compactor_metrics: Arc::new(CompactorMetrics::unused()),
+                    registry,
+                }
+            }
         };
 
         let state_store_impl = StateStoreImpl::new(
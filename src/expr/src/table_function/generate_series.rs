@@ -18,8 +18,8 @@ use anyhow::anyhow;
 use itertools::multizip;
 use num_traits::Zero;
 use risingwave_common::array::{
-    Array, ArrayBuilder, ArrayImpl, ArrayRef, DataChunk, I32Array, IntervalArray,
-    NaiveDateTimeArray,
+    Array, ArrayBuilder, ArrayImpl, ArrayRef, DataChunk, DecimalArray, I16Array, I32Array,
+    I64Array, IntervalArray, NaiveDateArray, NaiveDateTimeArray, TimestamptzArray,
 };
 use risingwave_common::types::{CheckedAdd, IsNegative, Scalar, ScalarRef};
 use risingwave_common::util::iter_util::ZipEqDebug;
@@ -57,12 +57,15 @@ where
         }
     }
 
+    /// Evaluate a single input row, returning the generated series split into chunks of at
+    /// most `self.chunk_size` rows each, rather than one giant array holding the whole
+    /// series. This keeps memory bounded for series with many elements.
     fn eval_row(
         &self,
         start: T::RefItem<'_>,
         stop: T::RefItem<'_>,
         step: S::RefItem<'_>,
-    ) -> Result<ArrayRef> {
+    ) -> Result<Vec<ArrayRef>> {
         if step.is_zero() {
             return Err(ExprError::InvalidParam {
                 name: "step",
@@ -70,7 +73,9 @@ where
             });
         }
 
+        let mut arrays = vec![];
         let mut builder = T::Builder::new(self.chunk_size);
+        let mut builder_len = 0;
 
         let mut cur: T::OwnedItem = start.to_owned_scalar();
 
@@ -85,11 +90,22 @@ where
         } else {
             cur < stop
         } {
+            if builder_len == self.chunk_size {
+                let full_builder =
+                    std::mem::replace(&mut builder, T::Builder::new(self.chunk_size));
+                arrays.push(Arc::new(full_builder.finish().into()) as ArrayRef);
+                builder_len = 0;
+            }
             builder.append(Some(cur.as_scalar_ref()));
+            builder_len += 1;
             cur = cur.checked_add(step).ok_or(ExprError::NumericOutOfRange)?;
         }
 
-        Ok(Arc::new(builder.finish().into()))
+        if builder_len > 0 || arrays.is_empty() {
+            arrays.push(Arc::new(builder.finish().into()) as ArrayRef);
+        }
+
+        Ok(arrays)
     }
 }
 
@@ -124,26 +140,24 @@ where
                     multizip((arr_start.iter(), arr_stop.iter(), arr_step.iter()))
                         .zip_eq_debug(bitmap.iter())
                 {
-                    let array = if !visible {
-                        empty_array(self.return_type())
+                    if !visible {
+                        output_arrays.push(empty_array(self.return_type()));
                     } else if let (Some(start), Some(stop), Some(step)) = (start, stop, step) {
-                        self.eval_row(start, stop, step)?
+                        output_arrays.extend(self.eval_row(start, stop, step)?);
                     } else {
-                        empty_array(self.return_type())
-                    };
-                    output_arrays.push(array);
+                        output_arrays.push(empty_array(self.return_type()));
+                    }
                 }
             }
             None => {
                 for (start, stop, step) in
                     multizip((arr_start.iter(), arr_stop.iter(), arr_step.iter()))
                 {
-                    let array = if let (Some(start), Some(stop), Some(step)) = (start, stop, step) {
-                        self.eval_row(start, stop, step)?
+                    if let (Some(start), Some(stop), Some(step)) = (start, stop, step) {
+                        output_arrays.extend(self.eval_row(start, stop, step)?);
                     } else {
-                        empty_array(self.return_type())
-                    };
-                    output_arrays.push(array);
+                        output_arrays.push(empty_array(self.return_type()));
+                    }
                 }
             }
         }
@@ -167,10 +181,36 @@ pub fn new_generate_series<const STOP_INCLUSIVE: bool>(
             STOP_INCLUSIVE,
         >::new(start, stop, step, chunk_size)
         .boxed()),
+        DataType::Date => Ok(
+            GenerateSeries::<NaiveDateArray, IntervalArray, STOP_INCLUSIVE>::new(
+                start, stop, step, chunk_size,
+            )
+            .boxed(),
+        ),
+        DataType::Timestamptz => Ok(GenerateSeries::<
+            TimestamptzArray,
+            IntervalArray,
+            STOP_INCLUSIVE,
+        >::new(start, stop, step, chunk_size)
+        .boxed()),
+        DataType::Int16 => Ok(GenerateSeries::<I16Array, I16Array, STOP_INCLUSIVE>::new(
+            start, stop, step, chunk_size,
+        )
+        .boxed()),
         DataType::Int32 => Ok(GenerateSeries::<I32Array, I32Array, STOP_INCLUSIVE>::new(
             start, stop, step, chunk_size,
         )
         .boxed()),
+        DataType::Int64 => Ok(GenerateSeries::<I64Array, I64Array, STOP_INCLUSIVE>::new(
+            start, stop, step, chunk_size,
+        )
+        .boxed()),
+        DataType::Decimal => Ok(
+            GenerateSeries::<DecimalArray, DecimalArray, STOP_INCLUSIVE>::new(
+                start, stop, step, chunk_size,
+            )
+            .boxed(),
+        ),
         _ => Err(ExprError::Internal(anyhow!(
             "the return type of Generate Series Function is incorrect".to_string(),
         ))),
@@ -179,11 +219,13 @@ pub fn new_generate_series<const STOP_INCLUSIVE: bool>(
 
 #[cfg(test)]
 mod tests {
-    use risingwave_common::types::{DataType, IntervalUnit, NaiveDateTimeWrapper, ScalarImpl};
+    use risingwave_common::types::{
+        DataType, IntervalUnit, NaiveDateTimeWrapper, ScalarImpl, Timestamptz,
+    };
 
     use super::*;
     use crate::expr::{Expression, LiteralExpression};
-    use crate::vector_op::cast::str_to_timestamp;
+    use crate::vector_op::cast::{str_to_timestamp, str_to_timestamptz};
 
     const CHUNK_SIZE: usize = 1024;
 
@@ -216,6 +258,76 @@ mod tests {
         assert_eq!(cnt, expect_cnt);
     }
 
+    /// Unlike `generate_series_test_case` (which only checks the total row count across all
+    /// returned arrays), this asserts the chunking itself: every array but the last must hold
+    /// exactly `CHUNK_SIZE` rows, and the values must be contiguous across the chunk boundary,
+    /// i.e. `eval_row`'s builder-flush logic isn't dropping or duplicating rows at the split.
+    #[test]
+    fn test_generate_i32_series_chunk_boundaries() {
+        fn to_lit_expr(v: i32) -> BoxedExpression {
+            LiteralExpression::new(DataType::Int32, Some(v.into())).boxed()
+        }
+
+        let start = 0;
+        let stop = (CHUNK_SIZE * 2 + 3) as i32;
+        let function = GenerateSeries::<I32Array, I32Array, true>::new(
+            to_lit_expr(start),
+            to_lit_expr(stop),
+            to_lit_expr(1),
+            CHUNK_SIZE,
+        )
+        .boxed();
+
+        let dummy_chunk = DataChunk::new_dummy(1);
+        let arrays = function.eval(&dummy_chunk).unwrap();
+
+        // 2*CHUNK_SIZE + 4 rows (inclusive stop) split into CHUNK_SIZE-sized chunks: two full
+        // chunks plus one final chunk of the remainder.
+        assert_eq!(arrays.len(), 3);
+        assert_eq!(arrays[0].len(), CHUNK_SIZE);
+        assert_eq!(arrays[1].len(), CHUNK_SIZE);
+        assert_eq!(arrays[2].len(), 4);
+
+        let as_i32 = |a: &ArrayRef, i: usize| a.as_int32().value_at(i).unwrap();
+        assert_eq!(as_i32(&arrays[0], 0), start);
+        assert_eq!(
+            as_i32(&arrays[0], CHUNK_SIZE - 1),
+            start + CHUNK_SIZE as i32 - 1
+        );
+        // First row of the second chunk must continue immediately after the first chunk's last.
+        assert_eq!(as_i32(&arrays[1], 0), start + CHUNK_SIZE as i32);
+        assert_eq!(as_i32(&arrays[2], 0), start + 2 * CHUNK_SIZE as i32);
+        assert_eq!(as_i32(&arrays[2], 3), stop);
+    }
+
+    #[test]
+    fn test_generate_i64_series() {
+        generate_i64_series_test_case(2, 4, 1);
+        generate_i64_series_test_case(4, 2, -1);
+        generate_i64_series_test_case(0, 9, 2);
+    }
+
+    fn generate_i64_series_test_case(start: i64, stop: i64, step: i64) {
+        fn to_lit_expr(v: i64) -> BoxedExpression {
+            LiteralExpression::new(DataType::Int64, Some(v.into())).boxed()
+        }
+
+        let function = GenerateSeries::<I64Array, I64Array, true>::new(
+            to_lit_expr(start),
+            to_lit_expr(stop),
+            to_lit_expr(step),
+            CHUNK_SIZE,
+        )
+        .boxed();
+        let expect_cnt = ((stop - start) / step + 1) as usize;
+
+        let dummy_chunk = DataChunk::new_dummy(1);
+        let arrays = function.eval(&dummy_chunk).unwrap();
+
+        let cnt: usize = arrays.iter().map(|a| a.len()).sum();
+        assert_eq!(cnt, expect_cnt);
+    }
+
     #[test]
     fn test_generate_time_series() {
         let start_time = str_to_timestamp("2008-03-01 00:00:00").unwrap();
@@ -253,6 +365,39 @@ mod tests {
         assert_eq!(cnt, expect_cnt);
     }
 
+    #[test]
+    fn test_generate_timestamptz_series() {
+        let start_time = str_to_timestamptz("2008-03-01 00:00:00+00:00").unwrap();
+        let stop_time = str_to_timestamptz("2008-03-09 00:00:00+00:00").unwrap();
+        let one_day_step = IntervalUnit::from_days(1);
+        generate_timestamptz_series_test_case(start_time, stop_time, one_day_step, 8 + 1);
+        generate_timestamptz_series_test_case(stop_time, start_time, -one_day_step, 8 + 1);
+    }
+
+    fn generate_timestamptz_series_test_case(
+        start: Timestamptz,
+        stop: Timestamptz,
+        step: IntervalUnit,
+        expect_cnt: usize,
+    ) {
+        fn to_lit_expr(ty: DataType, v: ScalarImpl) -> BoxedExpression {
+            LiteralExpression::new(ty, Some(v)).boxed()
+        }
+
+        let function = GenerateSeries::<TimestamptzArray, IntervalArray, true>::new(
+            to_lit_expr(DataType::Timestamptz, start.into()),
+            to_lit_expr(DataType::Timestamptz, stop.into()),
+            to_lit_expr(DataType::Interval, step.into()),
+            CHUNK_SIZE,
+        );
+
+        let dummy_chunk = DataChunk::new_dummy(1);
+        let arrays = function.eval(&dummy_chunk).unwrap();
+
+        let cnt: usize = arrays.iter().map(|a| a.len()).sum();
+        assert_eq!(cnt, expect_cnt);
+    }
+
     #[test]
     fn test_i32_range() {
         range_test_case(2, 4, 1);
@@ -0,0 +1,168 @@
+// Copyright 2023 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use risingwave_common::array::{Array, ArrayRef, BoolArray, DataChunk, I32Array, ListArray};
+
+use super::*;
+
+/// `generate_subscripts(array, dim [, reverse])` emits the valid subscripts (`1`-based) for
+/// dimension `dim` of `array`, optionally in reverse order. Only `dim = 1` is supported, as
+/// values in risingwave's `ListArray` are flat rather than genuinely multi-dimensional.
+#[derive(Debug)]
+pub struct GenerateSubscripts {
+    list: BoxedExpression,
+    dim: BoxedExpression,
+    reverse: Option<BoxedExpression>,
+    chunk_size: usize,
+}
+
+impl GenerateSubscripts {
+    fn eval_row(&self, len: usize, dim: i32, reverse: bool) -> Result<ArrayRef> {
+        let mut builder = <I32Array as Array>::Builder::new(self.chunk_size);
+        if dim == 1 {
+            if reverse {
+                for i in (1..=len as i32).rev() {
+                    builder.append(Some(i));
+                }
+            } else {
+                for i in 1..=len as i32 {
+                    builder.append(Some(i));
+                }
+            }
+        }
+        Ok(Arc::new(builder.finish().into()))
+    }
+}
+
+impl TableFunction for GenerateSubscripts {
+    fn return_type(&self) -> DataType {
+        DataType::Int32
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<Vec<ArrayRef>> {
+        let ret_list = self.list.eval_checked(input)?;
+        let arr_list: &ListArray = ret_list.as_ref().into();
+        let ret_dim = self.dim.eval_checked(input)?;
+        let arr_dim: &I32Array = ret_dim.as_ref().into();
+        let ret_reverse = self
+            .reverse
+            .as_ref()
+            .map(|e| e.eval_checked(input))
+            .transpose()?;
+        let arr_reverse: Option<&BoolArray> = ret_reverse.as_ref().map(|a| a.as_ref().into());
+
+        let bitmap = input.visibility();
+        let mut output_arrays: Vec<ArrayRef> = vec![];
+
+        for row_idx in 0..input.capacity() {
+            let visible = bitmap.as_ref().map_or(true, |b| b.is_set(row_idx));
+            let array = if !visible {
+                empty_array(self.return_type())
+            } else if let (Some(list), Some(dim)) =
+                (arr_list.value_at(row_idx), arr_dim.value_at(row_idx))
+            {
+                let reverse = arr_reverse
+                    .and_then(|r| r.value_at(row_idx))
+                    .unwrap_or(false);
+                self.eval_row(list.flatten().len(), dim, reverse)?
+            } else {
+                empty_array(self.return_type())
+            };
+            output_arrays.push(array);
+        }
+
+        Ok(output_arrays)
+    }
+}
+
+pub fn new_generate_subscripts(
+    prost: &TableFunctionProst,
+    chunk_size: usize,
+) -> Result<BoxedTableFunction> {
+    let mut args: Vec<_> = prost.args.iter().map(expr_build_from_prost).try_collect()?;
+    let reverse = if args.len() == 3 {
+        Some(args.pop().unwrap())
+    } else {
+        None
+    };
+    let [list, dim]: [_; 2] = args.try_into().unwrap();
+
+    Ok(GenerateSubscripts {
+        list,
+        dim,
+        reverse,
+        chunk_size,
+    }
+    .boxed())
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::types::DataType;
+
+    use super::*;
+    use crate::expr::LiteralExpression;
+
+    fn new_generate_subscripts(len: usize) -> GenerateSubscripts {
+        GenerateSubscripts {
+            list: LiteralExpression::new(
+                DataType::List {
+                    datatype: Box::new(DataType::Int32),
+                },
+                None,
+            )
+            .boxed(),
+            dim: LiteralExpression::new(DataType::Int32, Some(1.into())).boxed(),
+            reverse: None,
+            chunk_size: len.max(1),
+        }
+    }
+
+    fn to_i32s(array: ArrayRef) -> Vec<i32> {
+        let array: &I32Array = array.as_ref().into();
+        array.iter().map(|v| v.unwrap()).collect()
+    }
+
+    #[test]
+    fn test_eval_row_dim1() {
+        let function = new_generate_subscripts(5);
+        let array = function.eval_row(5, 1, false).unwrap();
+        assert_eq!(to_i32s(array), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_eval_row_dim1_reverse() {
+        let function = new_generate_subscripts(5);
+        let array = function.eval_row(5, 1, true).unwrap();
+        assert_eq!(to_i32s(array), vec![5, 4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_eval_row_empty_list() {
+        let function = new_generate_subscripts(1);
+        let array = function.eval_row(0, 1, false).unwrap();
+        assert!(to_i32s(array).is_empty());
+    }
+
+    /// Only `dim = 1` is supported; any other dimension yields no subscripts, matching
+    /// `eval_row`'s `if dim == 1` guard.
+    #[test]
+    fn test_eval_row_unsupported_dim() {
+        let function = new_generate_subscripts(3);
+        let array = function.eval_row(3, 2, false).unwrap();
+        assert!(to_i32s(array).is_empty());
+    }
+}
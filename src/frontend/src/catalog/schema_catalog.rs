@@ -16,6 +16,7 @@ use std::collections::hash_map::Entry::{Occupied, Vacant};
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use regex::Regex;
 use risingwave_common::catalog::{valid_table_name, FunctionId, IndexId, TableId};
 use risingwave_common::types::DataType;
 use risingwave_connector::sink::catalog::SinkCatalog;
@@ -57,6 +58,14 @@ pub struct SchemaCatalog {
     // This field only available when schema is "pg_catalog". Meanwhile, others will be empty.
     system_table_by_name: HashMap<String, SystemCatalog>,
     owner: u32,
+
+    // `COMMENT ON` descriptions, keyed by the id of the object they were attached to.
+    // Entries are absent unless a comment has been set, so these maps stay empty for the
+    // common case.
+    table_comment_by_id: HashMap<TableId, String>,
+    source_comment_by_id: HashMap<SourceId, String>,
+    sink_comment_by_id: HashMap<SinkId, String>,
+    view_comment_by_id: HashMap<ViewId, String>,
 }
 
 impl SchemaCatalog {
@@ -92,6 +101,7 @@ impl SchemaCatalog {
         let table_ref = self.table_by_id.remove(&id).unwrap();
         self.table_by_name.remove(&table_ref.name).unwrap();
         self.indexes_by_table_id.remove(&table_ref.id);
+        self.table_comment_by_id.remove(&id);
     }
 
     pub fn create_index(&mut self, prost: &ProstIndex) {
@@ -150,6 +160,7 @@ impl SchemaCatalog {
     pub fn drop_source(&mut self, id: SourceId) {
         let source_ref = self.source_by_id.remove(&id).unwrap();
         self.source_by_name.remove(&source_ref.name).unwrap();
+        self.source_comment_by_id.remove(&id);
     }
 
     pub fn create_sink(&mut self, prost: &ProstSink) {
@@ -167,6 +178,7 @@ impl SchemaCatalog {
     pub fn drop_sink(&mut self, id: SinkId) {
         let sink_ref = self.sink_by_id.remove(&id).unwrap();
         self.sink_by_name.remove(&sink_ref.name).unwrap();
+        self.sink_comment_by_id.remove(&id);
     }
 
     pub fn create_view(&mut self, prost: &ProstView) {
@@ -184,6 +196,7 @@ impl SchemaCatalog {
     pub fn drop_view(&mut self, id: ViewId) {
         let view_ref = self.view_by_id.remove(&id).unwrap();
         self.view_by_name.remove(&view_ref.name).unwrap();
+        self.view_comment_by_id.remove(&id);
     }
 
     pub fn create_function(&mut self, prost: &ProstFunction) {
@@ -248,6 +261,11 @@ impl SchemaCatalog {
         self.index_by_name.values()
     }
 
+    /// Iterate all user-defined functions, across all overloads of a given name.
+    pub fn iter_function(&self) -> impl Iterator<Item = &Arc<FunctionCatalog>> {
+        self.function_by_name.values().flat_map(|m| m.values())
+    }
+
     /// Iterate all sources
     pub fn iter_source(&self) -> impl Iterator<Item = &Arc<SourceCatalog>> {
         self.source_by_name.values()
@@ -265,6 +283,44 @@ impl SchemaCatalog {
         self.system_table_by_name.values()
     }
 
+    /// Iterate all valid tables (see [`Self::iter_valid_table`]) whose name matches a
+    /// `LIKE`-style `pattern` (`%`/`_` wildcards, as used by `SHOW TABLES LIKE`).
+    pub fn iter_table_by_pattern<'a>(
+        &'a self,
+        pattern: &str,
+    ) -> impl Iterator<Item = &'a Arc<TableCatalog>> {
+        let regex = like_pattern_to_regex(pattern);
+        self.iter_valid_table()
+            .filter(move |t| regex.is_match(&t.name))
+    }
+
+    /// Iterate all sources whose name matches a `LIKE`-style `pattern`.
+    pub fn iter_source_by_pattern<'a>(
+        &'a self,
+        pattern: &str,
+    ) -> impl Iterator<Item = &'a Arc<SourceCatalog>> {
+        let regex = like_pattern_to_regex(pattern);
+        self.iter_source().filter(move |s| regex.is_match(&s.name))
+    }
+
+    /// Iterate all sinks whose name matches a `LIKE`-style `pattern`.
+    pub fn iter_sink_by_pattern<'a>(
+        &'a self,
+        pattern: &str,
+    ) -> impl Iterator<Item = &'a Arc<SinkCatalog>> {
+        let regex = like_pattern_to_regex(pattern);
+        self.iter_sink().filter(move |s| regex.is_match(&s.name))
+    }
+
+    /// Iterate all views whose name matches a `LIKE`-style `pattern`.
+    pub fn iter_view_by_pattern<'a>(
+        &'a self,
+        pattern: &str,
+    ) -> impl Iterator<Item = &'a Arc<ViewCatalog>> {
+        let regex = like_pattern_to_regex(pattern);
+        self.iter_view().filter(move |v| regex.is_match(&v.name))
+    }
+
     pub fn get_table_by_name(&self, table_name: &str) -> Option<&Arc<TableCatalog>> {
         self.table_by_name.get(table_name)
     }
@@ -318,6 +374,70 @@ impl SchemaCatalog {
         self.function_by_name.get(name)?.get(args)
     }
 
+    /// Set or clear (`comment = None`) the `COMMENT ON TABLE` description for `table_id`.
+    pub fn comment_on_table(&mut self, table_id: TableId, comment: Option<String>) {
+        match comment {
+            Some(comment) => {
+                self.table_comment_by_id.insert(table_id, comment);
+            }
+            None => {
+                self.table_comment_by_id.remove(&table_id);
+            }
+        }
+    }
+
+    pub fn get_table_comment(&self, table_id: &TableId) -> Option<&str> {
+        self.table_comment_by_id.get(table_id).map(String::as_str)
+    }
+
+    /// Set or clear (`comment = None`) the `COMMENT ON SOURCE` description for `source_id`.
+    pub fn comment_on_source(&mut self, source_id: SourceId, comment: Option<String>) {
+        match comment {
+            Some(comment) => {
+                self.source_comment_by_id.insert(source_id, comment);
+            }
+            None => {
+                self.source_comment_by_id.remove(&source_id);
+            }
+        }
+    }
+
+    pub fn get_source_comment(&self, source_id: SourceId) -> Option<&str> {
+        self.source_comment_by_id.get(&source_id).map(String::as_str)
+    }
+
+    /// Set or clear (`comment = None`) the `COMMENT ON SINK` description for `sink_id`.
+    pub fn comment_on_sink(&mut self, sink_id: SinkId, comment: Option<String>) {
+        match comment {
+            Some(comment) => {
+                self.sink_comment_by_id.insert(sink_id, comment);
+            }
+            None => {
+                self.sink_comment_by_id.remove(&sink_id);
+            }
+        }
+    }
+
+    pub fn get_sink_comment(&self, sink_id: SinkId) -> Option<&str> {
+        self.sink_comment_by_id.get(&sink_id).map(String::as_str)
+    }
+
+    /// Set or clear (`comment = None`) the `COMMENT ON VIEW` description for `view_id`.
+    pub fn comment_on_view(&mut self, view_id: ViewId, comment: Option<String>) {
+        match comment {
+            Some(comment) => {
+                self.view_comment_by_id.insert(view_id, comment);
+            }
+            None => {
+                self.view_comment_by_id.remove(&view_id);
+            }
+        }
+    }
+
+    pub fn get_view_comment(&self, view_id: &ViewId) -> Option<&str> {
+        self.view_comment_by_id.get(view_id).map(String::as_str)
+    }
+
     pub fn id(&self) -> SchemaId {
         self.id
     }
@@ -351,6 +471,32 @@ impl From<&ProstSchema> for SchemaCatalog {
             view_by_id: HashMap::new(),
             function_by_name: HashMap::new(),
             function_by_id: HashMap::new(),
+            table_comment_by_id: HashMap::new(),
+            source_comment_by_id: HashMap::new(),
+            sink_comment_by_id: HashMap::new(),
+            view_comment_by_id: HashMap::new(),
+        }
+    }
+}
+
+/// Translate a SQL `LIKE` pattern (`%` = any sequence, `_` = any single char, `\` escapes
+/// the next char) into an anchored, case-sensitive [`Regex`].
+pub(crate) fn like_pattern_to_regex(pattern: &str) -> Regex {
+    let mut regex = String::with_capacity(pattern.len() + 2);
+    regex.push('^');
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '%' => regex.push_str(".*"),
+            '_' => regex.push('.'),
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    regex.push_str(&regex::escape(&escaped.to_string()));
+                }
+            }
+            _ => regex.push_str(&regex::escape(&c.to_string())),
         }
     }
+    regex.push('$');
+    Regex::new(&regex).expect("LIKE pattern must always translate to a valid regex")
 }
@@ -0,0 +1,112 @@
+// Copyright 2023 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Row-producing helpers for the `information_schema.schemata` / `.tables` / `.columns` virtual
+//! schema. Each walks the catalog the same way `SHOW SCHEMAS` / `SHOW TABLES` / `SHOW COLUMNS` do
+//! (see `crate::handler::show`), so they stay in sync as schemas/tables/columns are added.
+//!
+//! All three are reachable through [`crate::catalog::system_catalog::system_table_rows`], the
+//! dispatcher a `SELECT ... FROM information_schema.tables`-style scan resolves into.
+
+use pgwire::types::Row;
+use risingwave_common::error::Result;
+
+use crate::session::SessionImpl;
+
+/// One row of `information_schema.schemata`: `(catalog_name, schema_name)`.
+pub fn information_schema_schemata_rows(session: &SessionImpl) -> Result<Vec<Row>> {
+    let catalog_reader = session.env().catalog_reader().read_guard();
+    let db_name = session.database();
+
+    Ok(catalog_reader
+        .get_all_schema_names(db_name)?
+        .into_iter()
+        .map(|schema_name| {
+            Row::new(vec![
+                Some(db_name.to_string().into()),
+                Some(schema_name.into()),
+            ])
+        })
+        .collect())
+}
+
+/// One row of `information_schema.tables`: `(table_catalog, table_schema, table_name,
+/// table_type)`, where `table_type` is `"BASE TABLE"`, `"VIEW"`, or `"MATERIALIZED VIEW"`.
+pub fn information_schema_tables_rows(session: &SessionImpl) -> Result<Vec<Row>> {
+    let catalog_reader = session.env().catalog_reader().read_guard();
+    let db_name = session.database();
+    let mut rows = vec![];
+
+    for schema_name in catalog_reader.get_all_schema_names(db_name)? {
+        let schema = catalog_reader.get_schema_by_name(db_name, &schema_name)?;
+
+        for table in schema.iter_valid_table() {
+            let table_type = if table.is_mview() {
+                "MATERIALIZED VIEW"
+            } else {
+                "BASE TABLE"
+            };
+            rows.push(Row::new(vec![
+                Some(db_name.to_string().into()),
+                Some(schema_name.clone().into()),
+                Some(table.name.clone().into()),
+                Some(table_type.into()),
+            ]));
+        }
+
+        for view in schema.iter_view() {
+            rows.push(Row::new(vec![
+                Some(db_name.to_string().into()),
+                Some(schema_name.clone().into()),
+                Some(view.name.clone().into()),
+                Some("VIEW".into()),
+            ]));
+        }
+    }
+
+    Ok(rows)
+}
+
+/// One row of `information_schema.columns`: `(table_catalog, table_schema, table_name,
+/// column_name, ordinal_position, data_type)`.
+pub fn information_schema_columns_rows(session: &SessionImpl) -> Result<Vec<Row>> {
+    let catalog_reader = session.env().catalog_reader().read_guard();
+    let db_name = session.database();
+    let mut rows = vec![];
+
+    for schema_name in catalog_reader.get_all_schema_names(db_name)? {
+        let schema = catalog_reader.get_schema_by_name(db_name, &schema_name)?;
+
+        for table in schema.iter_valid_table() {
+            for (ordinal_position, column) in table
+                .columns
+                .iter()
+                .filter(|c| !c.is_hidden)
+                .enumerate()
+                .map(|(i, c)| (i + 1, c))
+            {
+                rows.push(Row::new(vec![
+                    Some(db_name.to_string().into()),
+                    Some(schema_name.clone().into()),
+                    Some(table.name.clone().into()),
+                    Some(column.column_desc.name.clone().into()),
+                    Some(ordinal_position.to_string().into()),
+                    Some(column.column_desc.data_type.to_string().into()),
+                ]));
+            }
+        }
+    }
+
+    Ok(rows)
+}
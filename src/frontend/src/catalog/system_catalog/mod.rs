@@ -0,0 +1,52 @@
+// Copyright 2023 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub mod information_schema;
+pub mod pg_catalog;
+
+use pgwire::types::Row;
+use risingwave_common::error::Result;
+
+use crate::session::SessionImpl;
+
+/// Looks up the row-producing function for one virtual system table by `(schema, table)` and
+/// runs it against `session`'s current catalog snapshot; `Ok(None)` means `(schema, table)` isn't
+/// one of the virtual tables this checkout models.
+///
+/// This is the seam a `SysCatalogReaderImpl`-style scan executor would call to answer `SELECT *
+/// FROM pg_catalog.pg_class` / `information_schema.tables` once `Relation::SystemTable` has been
+/// resolved to a schema/table name; the executor itself -- streaming these rows back through the
+/// normal query pipeline as a `TableScan` -- isn't part of this checkout.
+pub fn system_table_rows(
+    session: &SessionImpl,
+    schema: &str,
+    table: &str,
+) -> Result<Option<Vec<Row>>> {
+    let rows = match (schema, table) {
+        ("pg_catalog", "pg_namespace") => pg_catalog::pg_namespace_rows(session)?,
+        ("pg_catalog", "pg_class") => pg_catalog::pg_class_rows(session)?,
+        ("pg_catalog", "pg_attribute") => pg_catalog::pg_attribute_rows(session)?,
+        ("information_schema", "schemata") => {
+            information_schema::information_schema_schemata_rows(session)?
+        }
+        ("information_schema", "tables") => {
+            information_schema::information_schema_tables_rows(session)?
+        }
+        ("information_schema", "columns") => {
+            information_schema::information_schema_columns_rows(session)?
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(rows))
+}
@@ -0,0 +1,133 @@
+// Copyright 2023 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Row-producing helpers for a `pg_catalog` virtual schema: `pg_namespace`, `pg_class`, and
+//! `pg_attribute`, the three system tables PostgreSQL-aware tools (`psql \d`, JDBC/ODBC drivers,
+//! BI tools) query first to discover what's in a database. Each walks the catalog the same way
+//! `crate::handler::show` does, so they stay in sync as schemas/tables/columns are added.
+//!
+//! Real `pg_namespace`/`pg_class`/`pg_attribute` carry dozens of columns (ACLs, storage options,
+//! statistics targets, ...); only the handful most clients actually read are modeled here.
+//!
+//! Every oid below (`nspoid`, `reloid`) is synthesized by hashing the object's fully qualified
+//! name rather than tracking a real, stable Postgres-style oid allocator, since `SchemaCatalog`
+//! doesn't expose one. This is stable across calls within a process (same name -> same oid) but
+//! not guaranteed stable across restarts or comparable to any real Postgres installation's oids.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use pgwire::types::Row;
+use risingwave_common::error::Result;
+
+use crate::session::SessionImpl;
+
+fn oid_for(name: &str) -> i64 {
+    let mut hasher = DefaultHasher::new();
+    name.hash(&mut hasher);
+    // Keep it a positive, Postgres-oid-shaped 32-bit-looking number rather than a raw u64 hash.
+    (hasher.finish() & 0x7FFF_FFFF) as i64
+}
+
+/// One row of `pg_namespace`: `(oid, nspname)`, one per schema in the current database.
+pub fn pg_namespace_rows(session: &SessionImpl) -> Result<Vec<Row>> {
+    let catalog_reader = session.env().catalog_reader().read_guard();
+    let db_name = session.database();
+
+    Ok(catalog_reader
+        .get_all_schema_names(db_name)?
+        .into_iter()
+        .map(|schema_name| {
+            let oid = oid_for(&schema_name);
+            Row::new(vec![Some(oid.to_string().into()), Some(schema_name.into())])
+        })
+        .collect())
+}
+
+/// One row of `pg_class`: `(oid, relname, relnamespace, relkind)`, one per table, view,
+/// materialized view, source, sink, and index in the current database. `relkind` follows
+/// Postgres's convention: `r` (ordinary table), `v` (view), `m` (materialized view), `i` (index);
+/// sources and sinks have no real Postgres equivalent, so they're reported as `f` (foreign table),
+/// the closest existing `relkind` for "a relation backed by something outside Postgres itself".
+pub fn pg_class_rows(session: &SessionImpl) -> Result<Vec<Row>> {
+    let catalog_reader = session.env().catalog_reader().read_guard();
+    let db_name = session.database();
+    let mut rows = vec![];
+
+    for schema_name in catalog_reader.get_all_schema_names(db_name)? {
+        let schema = catalog_reader.get_schema_by_name(db_name, &schema_name)?;
+        let nsp_oid = oid_for(&schema_name);
+
+        let mut push = |rel_name: &str, relkind: &str| {
+            let rel_oid = oid_for(&format!("{schema_name}.{rel_name}"));
+            rows.push(Row::new(vec![
+                Some(rel_oid.to_string().into()),
+                Some(rel_name.to_string().into()),
+                Some(nsp_oid.to_string().into()),
+                Some(relkind.to_string().into()),
+            ]));
+        };
+
+        for table in schema.iter_valid_table() {
+            push(&table.name, if table.is_mview() { "m" } else { "r" });
+        }
+        for view in schema.iter_view() {
+            push(&view.name, "v");
+        }
+        for source in schema.iter_source() {
+            push(&source.name, "f");
+        }
+        for sink in schema.iter_sink() {
+            push(&sink.name, "f");
+        }
+        for index in schema.iter_index() {
+            push(&index.name, "i");
+        }
+    }
+
+    Ok(rows)
+}
+
+/// One row of `pg_attribute`: `(attrelid, attname, atttypid, attnum)`, one per non-hidden column
+/// of every table and mv in the current database; views get no rows here. `attrelid` matches the
+/// `oid` a corresponding `pg_class` row reports for the same relation.
+pub fn pg_attribute_rows(session: &SessionImpl) -> Result<Vec<Row>> {
+    let catalog_reader = session.env().catalog_reader().read_guard();
+    let db_name = session.database();
+    let mut rows = vec![];
+
+    for schema_name in catalog_reader.get_all_schema_names(db_name)? {
+        let schema = catalog_reader.get_schema_by_name(db_name, &schema_name)?;
+
+        for table in schema.iter_valid_table() {
+            let attrelid = oid_for(&format!("{schema_name}.{}", table.name));
+            for (attnum, column) in table
+                .columns
+                .iter()
+                .filter(|c| !c.is_hidden)
+                .enumerate()
+                .map(|(i, c)| (i + 1, c))
+            {
+                rows.push(Row::new(vec![
+                    Some(attrelid.to_string().into()),
+                    Some(column.column_desc.name.clone().into()),
+                    Some(column.column_desc.data_type.to_oid().to_string().into()),
+                    Some(attnum.to_string().into()),
+                ]));
+            }
+        }
+    }
+
+    Ok(rows)
+}
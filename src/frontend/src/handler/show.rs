@@ -12,32 +12,75 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashSet;
+
 use itertools::Itertools;
 use pgwire::pg_field_descriptor::PgFieldDescriptor;
 use pgwire::pg_response::{PgResponse, StatementType};
 use pgwire::types::Row;
-use risingwave_common::catalog::{ColumnDesc, DEFAULT_SCHEMA_NAME};
+use risingwave_common::catalog::{ColumnDesc, ColumnId, DEFAULT_SCHEMA_NAME};
 use risingwave_common::error::{ErrorCode, Result};
 use risingwave_common::types::DataType;
-use risingwave_sqlparser::ast::{Ident, ObjectName, ShowCreateType, ShowObject};
+use risingwave_sqlparser::ast::{
+    Ident, ObjectName, ShowCreateType, ShowObject, ShowStatementFilter,
+};
 
 use super::RwPgResponse;
 use crate::binder::{Binder, Relation};
+use crate::catalog::schema_catalog::like_pattern_to_regex;
 use crate::catalog::CatalogError;
-use crate::handler::util::col_descs_to_rows;
 use crate::handler::HandlerArgs;
 use crate::session::SessionImpl;
 
+/// A column plus the bits of `DESCRIBE`-style metadata that aren't part of `ColumnDesc`
+/// itself: whether it's part of the primary key, and whether any index covers it.
+pub struct ColumnCatalogInfo {
+    pub column_desc: ColumnDesc,
+    pub is_primary_key: bool,
+    pub is_indexed: bool,
+}
+
 pub fn get_columns_from_table(
     session: &SessionImpl,
     table_name: ObjectName,
-) -> Result<Vec<ColumnDesc>> {
+) -> Result<Vec<ColumnCatalogInfo>> {
     let mut binder = Binder::new(session);
     let relation = binder.bind_relation_by_name(table_name.clone(), None)?;
-    let catalogs = match relation {
-        Relation::Source(s) => s.catalog.columns,
-        Relation::BaseTable(t) => t.table_catalog.columns,
-        Relation::SystemTable(t) => t.sys_table_catalog.columns,
+    let (catalogs, pk_column_ids, indexed_column_ids) = match relation {
+        Relation::Source(s) => (s.catalog.columns, HashSet::new(), HashSet::new()),
+        Relation::BaseTable(t) => {
+            let catalog_reader = session.env().catalog_reader().read_guard();
+            let (schema_name, _) =
+                Binder::resolve_schema_qualified_name(session.database(), table_name.clone())?;
+            let schema = catalog_reader.get_schema_by_name(
+                session.database(),
+                &schema_name.unwrap_or(DEFAULT_SCHEMA_NAME.to_string()),
+            )?;
+            let indexed_column_ids: HashSet<ColumnId> = schema
+                .get_indexes_by_table_id(&t.table_id)
+                .into_iter()
+                .flat_map(|index| {
+                    index
+                        .index_table
+                        .columns
+                        .iter()
+                        .map(|c| c.column_desc.column_id)
+                        .collect_vec()
+                })
+                .collect();
+            let pk_column_ids: HashSet<ColumnId> = t
+                .table_catalog
+                .pk
+                .iter()
+                .map(|order| {
+                    t.table_catalog.columns[order.column_index]
+                        .column_desc
+                        .column_id
+                })
+                .collect();
+            (t.table_catalog.columns, pk_column_ids, indexed_column_ids)
+        }
+        Relation::SystemTable(t) => (t.sys_table_catalog.columns, HashSet::new(), HashSet::new()),
         _ => {
             return Err(CatalogError::NotFound("table or source", table_name.to_string()).into());
         }
@@ -46,7 +89,11 @@ pub fn get_columns_from_table(
     Ok(catalogs
         .iter()
         .filter(|c| !c.is_hidden)
-        .map(|c| c.column_desc.clone())
+        .map(|c| ColumnCatalogInfo {
+            is_primary_key: pk_column_ids.contains(&c.column_desc.column_id),
+            is_indexed: indexed_column_ids.contains(&c.column_desc.column_id),
+            column_desc: c.column_desc.clone(),
+        })
         .collect())
 }
 
@@ -56,47 +103,106 @@ fn schema_or_default(schema: &Option<Ident>) -> String {
         .map_or_else(|| DEFAULT_SCHEMA_NAME.to_string(), |s| s.real_value())
 }
 
+/// One row of a `pg_catalog.pg_tables`/`pg_views`/`pg_matviews`-shaped relational listing:
+/// `(schemaname, name, tableowner)`, where `tableowner` is the owner of the schema holding the
+/// object. `handle_show_object` builds these from the regular
+/// catalog walk so `SHOW` output has the same shape Postgres clients expect from those
+/// system views, rather than a single bare name column.
+type PgCatalogRow = (String, String, u32);
+
+/// Translate a MySQL-style `SHOW ... [LIKE 'pattern' | WHERE expr]` filter into a name
+/// predicate. `WHERE` is not implemented yet since it would need full expression binding
+/// against the synthesized SHOW result columns; only `LIKE` is supported for now.
+fn show_filter_predicate(filter: Option<ShowStatementFilter>) -> Result<Box<dyn Fn(&str) -> bool>> {
+    match filter {
+        None => Ok(Box::new(|_| true)),
+        Some(ShowStatementFilter::Like(pattern)) => {
+            let regex = like_pattern_to_regex(&pattern);
+            Ok(Box::new(move |name| regex.is_match(name)))
+        }
+        Some(ShowStatementFilter::Where(_)) => {
+            Err(ErrorCode::NotImplemented("SHOW ... WHERE".into(), None.into()).into())
+        }
+    }
+}
+
+/// Equivalent to [`handle_show_object_with_filter`] with no filter, kept so callers that don't
+/// need `LIKE`/`WHERE` filtering can keep passing just the command.
 pub fn handle_show_object(handler_args: HandlerArgs, command: ShowObject) -> Result<RwPgResponse> {
+    handle_show_object_with_filter(handler_args, command, None)
+}
+
+pub fn handle_show_object_with_filter(
+    handler_args: HandlerArgs,
+    command: ShowObject,
+    filter: Option<ShowStatementFilter>,
+) -> Result<RwPgResponse> {
     let session = handler_args.session;
     let catalog_reader = session.env().catalog_reader().read_guard();
+    let filter = show_filter_predicate(filter)?;
 
-    let names = match command {
-        // If not include schema name, use default schema name
-        ShowObject::Table { schema } => catalog_reader
-            .get_schema_by_name(session.database(), &schema_or_default(&schema))?
-            .iter_table()
-            .map(|t| t.name.clone())
-            .collect(),
-        ShowObject::InternalTable { schema } => catalog_reader
-            .get_schema_by_name(session.database(), &schema_or_default(&schema))?
-            .iter_internal_table()
-            .map(|t| t.name.clone())
-            .collect(),
-        ShowObject::Database => catalog_reader.get_all_database_names(),
-        ShowObject::Schema => catalog_reader.get_all_schema_names(session.database())?,
-        ShowObject::View { schema } => catalog_reader
-            .get_schema_by_name(session.database(), &schema_or_default(&schema))?
-            .iter_view()
-            .map(|t| t.name.clone())
-            .collect(),
-        ShowObject::MaterializedView { schema } => catalog_reader
-            .get_schema_by_name(session.database(), &schema_or_default(&schema))?
-            .iter_mv()
-            .map(|t| t.name.clone())
-            .collect(),
-        ShowObject::Source { schema } => catalog_reader
-            .get_schema_by_name(session.database(), &schema_or_default(&schema))?
-            .iter_source()
-            .map(|t| t.name.clone())
-            .collect(),
-        ShowObject::Sink { schema } => catalog_reader
-            .get_schema_by_name(session.database(), &schema_or_default(&schema))?
-            .iter_sink()
-            .map(|t| t.name.clone())
-            .collect(),
+    // `Database`/`Schema` are not scoped to a single schema, so they keep the plain
+    // single-column `Name` shape, and `Columns`/`Functions` report their own column sets.
+    // Everything else is a schema object and is reported in
+    // the same `(schemaname, name, owner)` shape as Postgres's `pg_catalog.pg_tables` /
+    // `pg_views` / `pg_matviews`, giving SHOW a relational, catalog-backed output.
+    let pg_catalog_rows: Vec<PgCatalogRow> = match command {
+        ShowObject::Database => {
+            let rows = catalog_reader
+                .get_all_database_names()
+                .into_iter()
+                .filter(|n| filter(n))
+                .map(|n| Row::new(vec![Some(n.into())]))
+                .collect_vec();
+            return Ok(PgResponse::new_for_stream(
+                StatementType::SHOW_COMMAND,
+                None,
+                rows.into(),
+                vec![PgFieldDescriptor::new(
+                    "Name".to_owned(),
+                    DataType::VARCHAR.to_oid(),
+                    DataType::VARCHAR.type_len(),
+                )],
+            ));
+        }
+        ShowObject::Schema => {
+            let rows = catalog_reader
+                .get_all_schema_names(session.database())?
+                .into_iter()
+                .filter(|n| filter(n))
+                .map(|n| Row::new(vec![Some(n.into())]))
+                .collect_vec();
+            return Ok(PgResponse::new_for_stream(
+                StatementType::SHOW_COMMAND,
+                None,
+                rows.into(),
+                vec![PgFieldDescriptor::new(
+                    "Name".to_owned(),
+                    DataType::VARCHAR.to_oid(),
+                    DataType::VARCHAR.type_len(),
+                )],
+            ));
+        }
         ShowObject::Columns { table } => {
-            let columns = get_columns_from_table(&session, table)?;
-            let rows = col_descs_to_rows(columns);
+            let columns = get_columns_from_table(&session, table)?
+                .into_iter()
+                .filter(|c| filter(&c.column_desc.name))
+                .collect_vec();
+            // A column is effectively NOT NULL when it's part of the primary key; risingwave
+            // doesn't otherwise track per-column nullability, and default expressions aren't
+            // tracked at all yet, so that column is always NULL here.
+            let rows = columns
+                .into_iter()
+                .map(|c| {
+                    Row::new(vec![
+                        Some(c.column_desc.name.into()),
+                        Some(c.column_desc.data_type.to_string().into()),
+                        Some((!c.is_primary_key).to_string().into()),
+                        None,
+                        Some((c.is_primary_key || c.is_indexed).to_string().into()),
+                    ])
+                })
+                .collect_vec();
 
             return Ok(PgResponse::new_for_stream(
                 StatementType::SHOW_COMMAND,
@@ -113,25 +219,160 @@ pub fn handle_show_object(handler_args: HandlerArgs, command: ShowObject) -> Res
                         DataType::VARCHAR.to_oid(),
                         DataType::VARCHAR.type_len(),
                     ),
+                    PgFieldDescriptor::new(
+                        "Is Nullable".to_owned(),
+                        DataType::VARCHAR.to_oid(),
+                        DataType::VARCHAR.type_len(),
+                    ),
+                    PgFieldDescriptor::new(
+                        "Default".to_owned(),
+                        DataType::VARCHAR.to_oid(),
+                        DataType::VARCHAR.type_len(),
+                    ),
+                    PgFieldDescriptor::new(
+                        "Is Key".to_owned(),
+                        DataType::VARCHAR.to_oid(),
+                        DataType::VARCHAR.type_len(),
+                    ),
+                ],
+            ));
+        }
+        ShowObject::Table { schema } => {
+            let schema = catalog_reader
+                .get_schema_by_name(session.database(), &schema_or_default(&schema))?;
+            schema
+                .iter_table()
+                .filter(|t| filter(&t.name))
+                .map(|t| (schema.name(), t.name.clone(), schema.owner()))
+                .collect_vec()
+        }
+        ShowObject::InternalTable { schema } => {
+            let schema = catalog_reader
+                .get_schema_by_name(session.database(), &schema_or_default(&schema))?;
+            schema
+                .iter_internal_table()
+                .filter(|t| filter(&t.name))
+                .map(|t| (schema.name(), t.name.clone(), schema.owner()))
+                .collect_vec()
+        }
+        ShowObject::View { schema } => {
+            let schema = catalog_reader
+                .get_schema_by_name(session.database(), &schema_or_default(&schema))?;
+            schema
+                .iter_view()
+                .filter(|t| filter(&t.name))
+                .map(|t| (schema.name(), t.name.clone(), schema.owner()))
+                .collect_vec()
+        }
+        ShowObject::MaterializedView { schema } => {
+            let schema = catalog_reader
+                .get_schema_by_name(session.database(), &schema_or_default(&schema))?;
+            schema
+                .iter_mv()
+                .filter(|t| filter(&t.name))
+                .map(|t| (schema.name(), t.name.clone(), schema.owner()))
+                .collect_vec()
+        }
+        ShowObject::Source { schema } => {
+            let schema = catalog_reader
+                .get_schema_by_name(session.database(), &schema_or_default(&schema))?;
+            schema
+                .iter_source()
+                .filter(|t| filter(&t.name))
+                .map(|t| (schema.name(), t.name.clone(), schema.owner()))
+                .collect_vec()
+        }
+        ShowObject::Sink { schema } => {
+            let schema = catalog_reader
+                .get_schema_by_name(session.database(), &schema_or_default(&schema))?;
+            schema
+                .iter_sink()
+                .filter(|t| filter(&t.name))
+                .map(|t| (schema.name(), t.name.clone(), schema.owner()))
+                .collect_vec()
+        }
+        ShowObject::Functions { schema } => {
+            // Only user-defined functions are tracked in the catalog; built-in scalar/table
+            // functions are resolved directly against the expression signature table at bind
+            // time and have no catalog entry to enumerate here.
+            let schema = catalog_reader
+                .get_schema_by_name(session.database(), &schema_or_default(&schema))?;
+            let rows = schema
+                .iter_function()
+                .filter(|f| filter(&f.name))
+                .map(|f| {
+                    let arg_types = f.arg_types.iter().map(|t| t.to_string()).join(", ");
+                    Row::new(vec![
+                        Some(f.name.clone().into()),
+                        Some(arg_types.into()),
+                        Some(f.return_type.to_string().into()),
+                        Some(format!("{:?}", f.kind).into()),
+                    ])
+                })
+                .collect_vec();
+
+            return Ok(PgResponse::new_for_stream(
+                StatementType::SHOW_COMMAND,
+                None,
+                rows.into(),
+                vec![
+                    PgFieldDescriptor::new(
+                        "Name".to_owned(),
+                        DataType::VARCHAR.to_oid(),
+                        DataType::VARCHAR.type_len(),
+                    ),
+                    PgFieldDescriptor::new(
+                        "Argument Types".to_owned(),
+                        DataType::VARCHAR.to_oid(),
+                        DataType::VARCHAR.type_len(),
+                    ),
+                    PgFieldDescriptor::new(
+                        "Return Type".to_owned(),
+                        DataType::VARCHAR.to_oid(),
+                        DataType::VARCHAR.type_len(),
+                    ),
+                    PgFieldDescriptor::new(
+                        "Kind".to_owned(),
+                        DataType::VARCHAR.to_oid(),
+                        DataType::VARCHAR.type_len(),
+                    ),
                 ],
             ));
         }
     };
 
-    let rows = names
+    let rows = pg_catalog_rows
         .into_iter()
-        .map(|n| Row::new(vec![Some(n.into())]))
+        .map(|(schema_name, name, owner)| {
+            Row::new(vec![
+                Some(schema_name.into()),
+                Some(name.into()),
+                Some(owner.to_string().into()),
+            ])
+        })
         .collect_vec();
 
     Ok(PgResponse::new_for_stream(
         StatementType::SHOW_COMMAND,
         None,
         rows.into(),
-        vec![PgFieldDescriptor::new(
-            "Name".to_owned(),
-            DataType::VARCHAR.to_oid(),
-            DataType::VARCHAR.type_len(),
-        )],
+        vec![
+            PgFieldDescriptor::new(
+                "Schema".to_owned(),
+                DataType::VARCHAR.to_oid(),
+                DataType::VARCHAR.type_len(),
+            ),
+            PgFieldDescriptor::new(
+                "Name".to_owned(),
+                DataType::VARCHAR.to_oid(),
+                DataType::VARCHAR.type_len(),
+            ),
+            PgFieldDescriptor::new(
+                "Owner".to_owned(),
+                DataType::VARCHAR.to_oid(),
+                DataType::VARCHAR.type_len(),
+            ),
+        ],
     ))
 }
 
@@ -167,12 +408,23 @@ pub fn handle_show_create_object(
                 .ok_or_else(|| CatalogError::NotFound("table", name.to_string()))?;
             table.create_sql()
         }
-        _ => {
-            return Err(ErrorCode::NotImplemented(
-                format!("show create on: {}", show_create_type),
-                None.into(),
-            )
-            .into());
+        ShowCreateType::Sink => {
+            let sink = schema
+                .get_sink_by_name(&object_name)
+                .ok_or_else(|| CatalogError::NotFound("sink", name.to_string()))?;
+            sink.create_sql()
+        }
+        ShowCreateType::Source => {
+            let source = schema
+                .get_source_by_name(&object_name)
+                .ok_or_else(|| CatalogError::NotFound("source", name.to_string()))?;
+            source.create_sql()
+        }
+        ShowCreateType::Index => {
+            let index = schema
+                .get_index_by_name(&object_name)
+                .ok_or_else(|| CatalogError::NotFound("index", name.to_string()))?;
+            index.create_sql()
         }
     };
     let name = format!("{}.{}", schema_name, object_name);
@@ -203,8 +455,38 @@ mod tests {
 
     use futures_async_stream::for_await;
 
+    use risingwave_sqlparser::ast::ShowStatementFilter;
+
+    use super::show_filter_predicate;
     use crate::test_utils::{create_proto_file, LocalFrontend, PROTO_FILE_DATA};
 
+    #[test]
+    fn test_show_filter_predicate_no_filter_accepts_everything() {
+        let filter = show_filter_predicate(None).unwrap();
+        assert!(filter("anything"));
+        assert!(filter(""));
+    }
+
+    #[test]
+    fn test_show_filter_predicate_like_matches_pattern() {
+        let filter =
+            show_filter_predicate(Some(ShowStatementFilter::Like("t_1".to_owned()))).unwrap();
+        assert!(filter("t11"));
+        assert!(!filter("t22"));
+        assert!(!filter("t111"));
+    }
+
+    #[test]
+    fn test_show_filter_predicate_where_is_not_implemented() {
+        let err = show_filter_predicate(Some(ShowStatementFilter::Where(
+            risingwave_sqlparser::ast::Expr::Identifier(
+                risingwave_sqlparser::ast::Ident::new_unchecked("x"),
+            ),
+        )))
+        .unwrap_err();
+        assert!(err.to_string().contains("SHOW ... WHERE"));
+    }
+
     #[tokio::test]
     async fn test_show_source() {
         let frontend = LocalFrontend::new(Default::default()).await;
@@ -216,7 +498,10 @@ mod tests {
 
         let mut rows = frontend.query_formatted_result("SHOW SOURCES").await;
         rows.sort();
-        assert_eq!(rows, vec!["Row([Some(b\"t1\")])".to_string(),]);
+        assert_eq!(
+            rows,
+            vec!["Row([Some(b\"public\"), Some(b\"t1\"), Some(b\"1\")])".to_string(),]
+        );
     }
 
     #[tokio::test]
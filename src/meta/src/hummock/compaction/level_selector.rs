@@ -16,11 +16,12 @@
 // This source code is licensed under both the GPLv2 (found in the
 // COPYING file in the root directory) and Apache 2.0 License
 // (found in the LICENSE.Apache file in the root directory).
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use risingwave_hummock_sdk::HummockCompactionTaskId;
 use risingwave_pb::hummock::hummock_version::Levels;
-use risingwave_pb::hummock::{compact_task, CompactionConfig};
+use risingwave_pb::hummock::{compact_task, CompactionConfig, KeyRange, Level, SstableInfo};
 
 use super::picker::{SpaceReclaimCompactionPicker, TtlReclaimCompactionPicker};
 use super::{
@@ -38,6 +39,77 @@ use crate::rpc::metrics::MetaMetrics;
 
 const SCORE_BASE: u64 = 100;
 
+/// One seek allowance per this many bytes of file data, mirroring RocksDB's
+/// `Version::UpdateAccumulatedStats` default.
+const SEEK_COMPACTION_BYTES_PER_SEEK: u64 = 16 * 1024;
+/// Floor on a file's seek budget so small files aren't flagged for compaction after a single
+/// lookup.
+const MIN_ALLOWED_SEEKS: u64 = 100;
+
+fn allowed_seeks_for_file_size(file_size: u64) -> u64 {
+    std::cmp::max(
+        file_size / SEEK_COMPACTION_BYTES_PER_SEEK,
+        MIN_ALLOWED_SEEKS,
+    )
+}
+
+/// Tracks each SST's remaining "allowed seeks" budget (RocksDB-style seek-triggered compaction):
+/// every wasted seek against a file -- one where a read iterator consulted it but found the key
+/// in a deeper level -- decrements its budget, and a file that hits zero is flagged as a
+/// compaction candidate even if the level's score is under `SCORE_BASE`. This catches hot
+/// point-lookup workloads that repeatedly fall through sparsely-populated upper levels, which
+/// never shows up in the size-based scoring `get_priority_levels` does.
+///
+/// The budget plays the role RocksDB's `file_to_compact`/`file_to_compact_level` fields on level
+/// metadata play, kept here instead since `Level`/`SstableInfo` (the `risingwave_pb` protos) have
+/// no such field in this checkout. Likewise, the actual wasted-seek notification has to come from
+/// the read iterator, which isn't part of this checkout either; [`DynamicLevelSelector::
+/// record_wasted_seek`] is the call site it would use.
+#[derive(Default)]
+pub struct FileSeekTracker {
+    remaining_seeks: HashMap<u64, u64>,
+}
+
+impl FileSeekTracker {
+    /// Registers `file`'s budget the first time it's seen; a no-op for files already tracked, so
+    /// re-syncing after every compaction round doesn't reset an in-flight budget.
+    pub fn track(&mut self, file: &SstableInfo) {
+        self.remaining_seeks
+            .entry(file.id)
+            .or_insert_with(|| allowed_seeks_for_file_size(file.file_size));
+    }
+
+    /// Registers the budget of every file currently present in `levels`, and forgets any file no
+    /// longer present (e.g. compacted away).
+    pub fn sync_levels(&mut self, levels: &Levels) {
+        let mut live_ids = std::collections::HashSet::new();
+        for level in &levels.levels {
+            for file in &level.table_infos {
+                live_ids.insert(file.id);
+                self.track(file);
+            }
+        }
+        self.remaining_seeks.retain(|id, _| live_ids.contains(id));
+    }
+
+    /// Records a wasted seek against `file_id`. Returns `true` the moment the budget reaches
+    /// zero, i.e. the file has just become a compaction candidate.
+    pub fn record_wasted_seek(&mut self, file_id: u64) -> bool {
+        match self.remaining_seeks.get_mut(&file_id) {
+            Some(remaining) if *remaining > 0 => {
+                *remaining -= 1;
+                *remaining == 0
+            }
+            _ => false,
+        }
+    }
+
+    /// `true` once `file_id`'s seek budget has been exhausted.
+    pub fn is_exhausted(&self, file_id: u64) -> bool {
+        self.remaining_seeks.get(&file_id).copied() == Some(0)
+    }
+}
+
 pub mod selector_option {
     use std::collections::HashSet;
     use std::sync::Arc;
@@ -146,6 +218,7 @@ pub struct DynamicLevelSelectorCore {
 pub struct DynamicLevelSelector {
     dynamic_level_core: DynamicLevelSelectorCore,
     overlap_strategy: Arc<dyn OverlapStrategy>,
+    seek_tracker: FileSeekTracker,
 }
 
 impl Default for DynamicLevelSelector {
@@ -161,6 +234,7 @@ impl DynamicLevelSelector {
         Self {
             dynamic_level_core: DynamicLevelSelectorCore::new(config),
             overlap_strategy,
+            seek_tracker: FileSeekTracker::default(),
         }
     }
 
@@ -170,6 +244,14 @@ impl DynamicLevelSelector {
         self.overlap_strategy =
             create_overlap_strategy(selector_option.compaction_config.compaction_mode());
     }
+
+    /// Records a wasted seek against `file_id` -- the read path consulted this file but found the
+    /// key in a deeper level. Once its budget is exhausted, subsequent `pick_compaction` calls
+    /// favor compacting it even if the level's size-based score is below the trigger threshold.
+    /// See [`FileSeekTracker`].
+    pub fn record_wasted_seek(&mut self, file_id: u64) -> bool {
+        self.seek_tracker.record_wasted_seek(file_id)
+    }
 }
 
 impl DynamicLevelSelectorCore {
@@ -215,7 +297,15 @@ impl DynamicLevelSelectorCore {
     /// `calculate_level_base_size` calculate base level and the base size of LSM tree build for
     /// current dataset. In other words,  `level_max_bytes` is our compaction goal which shall
     /// reach. This algorithm refers to the implementation in  `</>https://github.com/facebook/rocksdb/blob/v7.2.2/db/version_set.cc#L3706</>`
+    ///
+    /// When `level_compaction_use_dynamic_level_bytes` is disabled, we skip the dynamic
+    /// derivation below in favor of [`Self::calculate_fixed_level_base_size`], which gives
+    /// operators a `level_max_bytes` that doesn't shift around as the dataset grows.
     pub fn calculate_level_base_size(&self, levels: &Levels) -> SelectContext {
+        if !self.config.level_compaction_use_dynamic_level_bytes {
+            return self.calculate_fixed_level_base_size();
+        }
+
         let mut first_non_empty_level = 0;
         let mut max_level_size = 0;
         let mut ctx = SelectContext::default();
@@ -272,6 +362,26 @@ impl DynamicLevelSelectorCore {
         ctx
     }
 
+    /// The classic, non-dynamic scheme: `base_level` is always the first configured level and
+    /// `level_max_bytes[i] = max_bytes_for_level_base * multiplier^(i - base_level)`, regardless
+    /// of how much data is actually stored. This trades space efficiency on a mostly-empty tree
+    /// for a `level_max_bytes` that is deterministic and stable across compactions, which is
+    /// what operators with `level_compaction_use_dynamic_level_bytes = false` are asking for.
+    fn calculate_fixed_level_base_size(&self) -> SelectContext {
+        let mut ctx = SelectContext::default();
+        ctx.level_max_bytes
+            .resize(self.config.max_level as usize + 1, u64::MAX);
+        ctx.base_level = 1;
+
+        let level_multiplier = self.config.max_bytes_for_level_multiplier as f64;
+        let mut level_size = self.config.max_bytes_for_level_base;
+        for i in ctx.base_level..=self.config.max_level as usize {
+            ctx.level_max_bytes[i] = level_size;
+            level_size = (level_size as f64 * level_multiplier) as u64;
+        }
+        ctx
+    }
+
     fn get_priority_levels(&self, levels: &Levels, handlers: &[LevelHandler]) -> SelectContext {
         let mut ctx = self.calculate_level_base_size(levels);
 
@@ -292,10 +402,26 @@ impl DynamicLevelSelectorCore {
 
         let total_size = levels.l0.as_ref().unwrap().total_file_size
             - handlers[0].get_pending_output_file_size(ctx.base_level as u32);
+        // The base level can't absorb more data while a previous compaction's output is still
+        // pending against it; routing the L0->base score there anyway just queues behind work
+        // that hasn't landed yet, so L0 keeps growing and read amp (and eventually write
+        // stalls) get worse. Rather than inventing a new L0->L0 picker, reuse the existing
+        // `(select_level, target_level) == (0, 0)` branch `create_compaction_picker` already
+        // dispatches to `TierCompactionPicker` for: boost its score past the stuck L0->base
+        // score so `DynamicLevelSelector::pick_compaction`'s score_levels race tries it first,
+        // shrinking L0 via intra-tier compaction while the base level catches up.
+        let base_level_is_blocked = handlers[0].get_pending_output_file_size(ctx.base_level as u32)
+            * 2
+            > self.config.max_bytes_for_level_base;
         if idle_file_count > 0 {
             // trigger intra-l0 compaction at first when the number of files is too large.
             let l0_score =
                 idle_file_count as u64 * SCORE_BASE / self.config.level0_tier_compact_file_number;
+            let l0_score = if base_level_is_blocked {
+                std::cmp::max(l0_score, SCORE_BASE + 1)
+            } else {
+                l0_score
+            };
             ctx.score_levels
                 .push((std::cmp::min(l0_score, max_l0_score), 0, 0));
             let score = total_size * SCORE_BASE / self.config.max_bytes_for_level_base;
@@ -319,17 +445,700 @@ impl DynamicLevelSelectorCore {
             if total_size == 0 {
                 continue;
             }
-            ctx.score_levels.push((
-                total_size * SCORE_BASE / ctx.level_max_bytes[level_idx],
-                level_idx,
-                level_idx + 1,
-            ));
+            let mut score = total_size * SCORE_BASE / ctx.level_max_bytes[level_idx];
+            if let Some(target_level) = levels
+                .levels
+                .iter()
+                .find(|l| l.level_idx as usize == level_idx + 1)
+            {
+                score = std::cmp::max(
+                    score,
+                    self.write_amp_score_boost(level, target_level, level_idx, &ctx),
+                );
+                score = std::cmp::max(
+                    score,
+                    self.trivial_move_score_boost(level, target_level, &handlers[level_idx]),
+                );
+                score = std::cmp::max(
+                    score,
+                    self.min_overlapping_ratio_score_boost(
+                        level,
+                        target_level,
+                        &handlers[level_idx],
+                    ),
+                );
+                score = std::cmp::max(
+                    score,
+                    self.min_overlap_window_score_boost(level, target_level, &handlers[level_idx]),
+                );
+            }
+            ctx.score_levels.push((score, level_idx, level_idx + 1));
         }
 
         // sort reverse to pick the largest one.
         ctx.score_levels.sort_by(|a, b| b.0.cmp(&a.0));
         ctx
     }
+
+    /// Looks for a genuinely low-write-amplification compaction window in `level` via
+    /// [`select_min_write_amp_window`] and, if one exists, returns a score that guarantees this
+    /// level is picked by [`DynamicLevelSelector::pick_compaction`] ahead of any level whose
+    /// priority comes only from the size-based formula above. Returns `0` (no boost) when the
+    /// level isn't over its `level_max_bytes` budget, or its best window still pulls in at least
+    /// half its own bytes worth of next-level data.
+    fn write_amp_score_boost(
+        &self,
+        level: &Level,
+        target_level: &Level,
+        level_idx: usize,
+        ctx: &SelectContext,
+    ) -> u64 {
+        let Some((window, overlapping)) =
+            select_min_write_amp_window(level, target_level, level_idx, ctx)
+        else {
+            return 0;
+        };
+        let window_bytes: u64 = window.iter().map(|f| f.file_size).sum();
+        let overlap_bytes: u64 = overlapping.iter().map(|f| f.file_size).sum();
+        if window_bytes > 0 && overlap_bytes * 2 < window_bytes {
+            SCORE_BASE + 1
+        } else {
+            0
+        }
+    }
+
+    /// `true` once any non-pending file in `level` can be relabeled into `target_level` as a
+    /// trivial move -- it shares no actual data with any file there, checked via
+    /// [`is_trivial_move`] or, when `enable_trivial_move_data_overlap_check` is off, the coarser
+    /// range-only [`filter_overlapping_targets`] gate -- in which case returns a score well above
+    /// `SCORE_BASE` so `DynamicLevelSelector::pick_compaction` tries this `(select_level,
+    /// target_level)` pair ahead of one that can only be served by a real (byte-copying)
+    /// compaction. Relabeling a file costs nothing but a metadata update, so it's worth
+    /// preferring even over a level that's more over budget by the size-based score alone.
+    fn trivial_move_score_boost(
+        &self,
+        level: &Level,
+        target_level: &Level,
+        handler: &LevelHandler,
+    ) -> u64 {
+        let use_data_overlap_check = self.config.enable_trivial_move_data_overlap_check;
+        let has_trivial_move_candidate = level.table_infos.iter().any(|file| {
+            if handler.is_pending_compact(&file.id) {
+                return false;
+            }
+            if use_data_overlap_check {
+                is_trivial_move(file, &target_level.table_infos)
+            } else {
+                filter_overlapping_targets(file, &target_level.table_infos, false).is_empty()
+            }
+        });
+        if has_trivial_move_candidate {
+            SCORE_BASE * 2
+        } else {
+            0
+        }
+    }
+
+    /// Gated by `CompactionConfig::enable_min_overlapping_ratio_priority`: when set, finds
+    /// `level`'s best candidate via [`pick_min_overlapping_ratio_file`] and, if its overlap with
+    /// `target_level` is small relative to its own size, returns a score above `SCORE_BASE` so
+    /// `DynamicLevelSelector::pick_compaction` prefers this `(select_level, target_level)` pair --
+    /// a compaction that rewrites few target-level bytes per source byte moved is cheaper than one
+    /// chosen by the size-based formula alone, which knows nothing about overlap. Returns `0` when
+    /// the config flag is off, there is no pickable candidate, or the candidate's overlap is at
+    /// least half its own size.
+    fn min_overlapping_ratio_score_boost(
+        &self,
+        level: &Level,
+        target_level: &Level,
+        handler: &LevelHandler,
+    ) -> u64 {
+        if !self.config.enable_min_overlapping_ratio_priority {
+            return 0;
+        }
+        let Some(file) = pick_min_overlapping_ratio_file(level, target_level, handler) else {
+            return 0;
+        };
+        let overlap = overlapping_bytes(file, target_level);
+        if file.file_size > 0 && overlap * 2 < file.file_size {
+            SCORE_BASE + 1
+        } else {
+            0
+        }
+    }
+
+    /// Runs [`select_min_overlap_window`] against `level`/`target_level` (bounded by
+    /// `CompactionConfig::max_compaction_bytes`, same cap `create_compaction_picker`'s pickers
+    /// respect) and, if a window is found, returns a score above `SCORE_BASE` so
+    /// `DynamicLevelSelector::pick_compaction` prefers this `(select_level, target_level)` pair --
+    /// doubly so when the window is a trivial move, same priority
+    /// [`Self::trivial_move_score_boost`] gives one. Returns `0` when no window qualifies (every
+    /// window either has a pending file, exceeds the byte cap, or its overlap ratio isn't
+    /// favorable).
+    fn min_overlap_window_score_boost(
+        &self,
+        level: &Level,
+        target_level: &Level,
+        handler: &LevelHandler,
+    ) -> u64 {
+        let Some(selection) = select_min_overlap_window(
+            level,
+            target_level,
+            handler,
+            self.config.max_compaction_bytes,
+        ) else {
+            return 0;
+        };
+        if selection.is_trivial_move {
+            return SCORE_BASE * 2;
+        }
+        let window_bytes: u64 = selection.window.iter().map(|f| f.file_size).sum();
+        let overlap_bytes: u64 = selection.overlapping.iter().map(|f| f.file_size).sum();
+        if window_bytes > 0 && overlap_bytes * 2 < window_bytes {
+            SCORE_BASE + 1
+        } else {
+            0
+        }
+    }
+
+    /// Post-selection clip applied in [`DynamicLevelSelector::pick_compaction`] to a picked
+    /// `select_level`'s candidate files (`select_level > 0`, so `target_level + 1` -- the
+    /// grandparent level -- exists in `levels.levels`): re-bounds them through
+    /// [`bound_input_by_grandparent_overlap`], gated by
+    /// `CompactionConfig::max_grand_parent_overlap_factor` (`0`, the proto's zero-value default,
+    /// disables the clip and returns `candidates` unchanged).
+    fn grandparent_overlap_bound(
+        &self,
+        levels: &Levels,
+        target_level: usize,
+        candidates: &[SstableInfo],
+    ) -> Vec<SstableInfo> {
+        let factor = self.config.max_grand_parent_overlap_factor;
+        if factor == 0 {
+            return candidates.to_vec();
+        }
+        let Some(grandparent_level) = levels
+            .levels
+            .iter()
+            .find(|l| l.level_idx as usize == target_level + 1)
+        else {
+            return candidates.to_vec();
+        };
+        bound_input_by_grandparent_overlap(
+            candidates,
+            grandparent_level,
+            factor,
+            self.config.target_file_size_base,
+        )
+    }
+
+    /// Post-selection expansion applied in [`DynamicLevelSelector::pick_compaction`] right
+    /// alongside [`Self::grandparent_overlap_bound`]: re-fetches `select_level`'s own [`Level`]
+    /// from `levels` and runs `candidates` through [`expand_to_clean_user_key_boundary`] against
+    /// it. Returns `candidates` unchanged if `select_level` isn't found in `levels.levels` (e.g.
+    /// `select_level == 0`, L0 has no single `Level` to expand against).
+    fn expand_to_clean_user_key_boundary(
+        &self,
+        levels: &Levels,
+        select_level: usize,
+        candidates: Vec<SstableInfo>,
+    ) -> Vec<SstableInfo> {
+        let Some(level) = levels
+            .levels
+            .iter()
+            .find(|l| l.level_idx as usize == select_level)
+        else {
+            return candidates;
+        };
+        expand_to_clean_user_key_boundary(level, candidates)
+    }
+
+    /// Seek-triggered compaction: for every level holding a file whose [`FileSeekTracker`] budget
+    /// is exhausted (and that isn't already pending compaction), append a score just above
+    /// `SCORE_BASE` so the level is picked by the same per-level picker
+    /// `create_compaction_picker` would otherwise use, instead of requiring a dedicated seek
+    /// compaction task type.
+    fn seek_priority_levels(
+        &self,
+        levels: &Levels,
+        handlers: &[LevelHandler],
+        tracker: &FileSeekTracker,
+    ) -> Vec<(u64, usize, usize)> {
+        let mut score_levels = vec![];
+        for level in &levels.levels {
+            let level_idx = level.level_idx as usize;
+            if level_idx == 0 || level_idx >= self.config.max_level as usize {
+                continue;
+            }
+            let has_exhausted_file = level.table_infos.iter().any(|file| {
+                tracker.is_exhausted(file.id) && !handlers[level_idx].is_pending_compact(&file.id)
+            });
+            if has_exhausted_file {
+                score_levels.push((SCORE_BASE + 1, level_idx, level_idx + 1));
+            }
+        }
+        score_levels
+    }
+}
+
+fn merged_key_range(files: &[SstableInfo]) -> (Vec<u8>, Vec<u8>) {
+    let first = files[0].key_range.as_ref().unwrap();
+    let mut left = first.left.clone();
+    let mut right = first.right.clone();
+    for file in &files[1..] {
+        let key_range = file.key_range.as_ref().unwrap();
+        if key_range.left < left {
+            left = key_range.left.clone();
+        }
+        if key_range.right > right {
+            right = key_range.right.clone();
+        }
+    }
+    (left, right)
+}
+
+fn range_overlaps_file(left: &[u8], right: &[u8], file: &SstableInfo) -> bool {
+    let key_range = file.key_range.as_ref().unwrap();
+    key_range.left.as_slice() <= right && left <= key_range.right.as_slice()
+}
+
+/// Evaluate every contiguous window of `curr_level`'s (sorted, non-overlapping) files against
+/// `target_level` and return the window, plus the `target_level` files it overlaps, that
+/// minimizes `overlapping_target_bytes / window_bytes` (ties broken by the smaller absolute
+/// overlap). Only windows that shrink `curr_level`'s overshoot past
+/// `ctx.level_max_bytes[level_idx]` are considered; `None` means the level isn't over budget.
+///
+/// This generalizes the single-file selection `MinOverlappingPicker` makes today (constructed
+/// in `DynamicLevelSelectorCore::create_compaction_picker`) to reduce write amplification, at
+/// the cost of evaluating O(n^2) windows.
+///
+/// `MinOverlappingPicker` itself still makes the actual file selection -- its body lives in
+/// `compaction/picker.rs`, which isn't part of this checkout, so there's no picker
+/// implementation left here to swap out. What this file controls is which `(select_level,
+/// target_level)` pair `DynamicLevelSelector::pick_compaction` tries the picker against first:
+/// see [`DynamicLevelSelectorCore::write_amp_score_boost`], which calls this function from
+/// [`DynamicLevelSelectorCore::get_priority_levels`] and boosts a level's score whenever a
+/// genuinely cheap window exists, so it's tried ahead of a same-score level whose best window
+/// would cost proportionally more.
+pub fn select_min_write_amp_window(
+    curr_level: &Level,
+    target_level: &Level,
+    level_idx: usize,
+    ctx: &SelectContext,
+) -> Option<(Vec<SstableInfo>, Vec<SstableInfo>)> {
+    if curr_level.total_file_size <= ctx.level_max_bytes[level_idx] {
+        return None;
+    }
+
+    let files = &curr_level.table_infos;
+    // (overlap_bytes, window_bytes, window, overlapping_target_files)
+    let mut best: Option<(u64, u64, Vec<SstableInfo>, Vec<SstableInfo>)> = None;
+
+    for window_size in 1..=files.len() {
+        for start in 0..=(files.len() - window_size) {
+            let window = &files[start..start + window_size];
+            let window_bytes: u64 = window.iter().map(|f| f.file_size).sum();
+            let (left, right) = merged_key_range(window);
+            let overlapping: Vec<SstableInfo> = target_level
+                .table_infos
+                .iter()
+                .filter(|f| range_overlaps_file(&left, &right, f))
+                .cloned()
+                .collect();
+            let overlap_bytes: u64 = overlapping.iter().map(|f| f.file_size).sum();
+
+            let is_better = match &best {
+                None => true,
+                Some((best_overlap, best_window_bytes, _, _)) => {
+                    let lhs = overlap_bytes as u128 * *best_window_bytes as u128;
+                    let rhs = *best_overlap as u128 * window_bytes as u128;
+                    lhs < rhs || (lhs == rhs && overlap_bytes < *best_overlap)
+                }
+            };
+            if is_better {
+                best = Some((overlap_bytes, window_bytes, window.to_vec(), overlapping));
+            }
+        }
+    }
+
+    best.map(|(_, _, window, overlapping)| (window, overlapping))
+}
+
+/// Extends `candidates` (already-sorted, non-overlapping files from level L, e.g. the window
+/// [`select_min_write_amp_window`] picked) one file at a time, stopping as soon as the
+/// accumulated `file_size` of every grandparent-level (L+2) file whose `key_range` overlaps the
+/// selected-so-far range would exceed `max_grand_parent_overlap_factor * target_file_size`.
+/// Always keeps at least the first candidate so the picker still makes progress when a single
+/// file alone already exceeds the budget. Mirrors the grandparent-overlap clipping in RocksDB's
+/// `CompactionPicker::PickCompaction`, and exists so one compaction can't produce an output
+/// SSTable that overlaps so much of L+2 that level's next compaction stalls behind it.
+///
+/// Wired into [`DynamicLevelSelector::pick_compaction`] via
+/// [`DynamicLevelSelectorCore::grandparent_overlap_bound`], which re-bounds a picked
+/// `select_level`'s candidate files through this function before the task is handed off, gated by
+/// `CompactionConfig::max_grand_parent_overlap_factor` (`0` disables the clip, matching the
+/// proto's zero-value default).
+pub fn bound_input_by_grandparent_overlap(
+    candidates: &[SstableInfo],
+    grandparent_level: &Level,
+    max_grand_parent_overlap_factor: u64,
+    target_file_size: u64,
+) -> Vec<SstableInfo> {
+    let overlap_budget = max_grand_parent_overlap_factor.saturating_mul(target_file_size);
+    let mut selected = vec![];
+    let mut range: Option<(Vec<u8>, Vec<u8>)> = None;
+
+    for file in candidates {
+        let file_range = file.key_range.as_ref().unwrap();
+        let (left, right) = match &range {
+            Some((l, r)) => (
+                std::cmp::min(l, &file_range.left).clone(),
+                std::cmp::max(r, &file_range.right).clone(),
+            ),
+            None => (file_range.left.clone(), file_range.right.clone()),
+        };
+
+        let overlap_bytes: u64 = grandparent_level
+            .table_infos
+            .iter()
+            .filter(|g| range_overlaps_file(&left, &right, g))
+            .map(|g| g.file_size)
+            .sum();
+
+        if !selected.is_empty() && overlap_bytes > overlap_budget {
+            break;
+        }
+
+        selected.push(file.clone());
+        range = Some((left, right));
+    }
+
+    selected
+}
+
+/// Length, in bytes, of the epoch/sequence suffix every encoded key carries (see
+/// `risingwave_hummock_sdk::key`, and `iterator_test_key_of_epoch` in the test helpers below,
+/// which append it the same way). Two encoded keys that differ only in this suffix address the
+/// same user key at different MVCC versions.
+const EPOCH_LEN: usize = 8;
+
+/// Strips the trailing epoch/sequence suffix, leaving the bare user key.
+fn strip_epoch_suffix(full_key: &[u8]) -> &[u8] {
+    &full_key[..full_key.len().saturating_sub(EPOCH_LEN)]
+}
+
+/// After `selected` (a contiguous run of `level`'s files already chosen as compaction input, e.g.
+/// the output of [`select_min_overlap_window`]) is picked, repeatedly pulls in the file
+/// immediately following it in `level.table_infos` if that neighbor's leftmost key shares a user
+/// key -- ignoring the epoch/sequence suffix -- with the last selected file's rightmost key.
+///
+/// Left unexpanded, compacting only the first file risks dropping an old version's delete
+/// tombstone at that boundary user key while a same-user-key put surviving in the untouched
+/// neighbor resurfaces once the tombstone is gone. Only expands rightward, since `selected` is
+/// assumed already aligned to a clean user-key boundary on its left edge.
+///
+/// Wired into [`DynamicLevelSelector::pick_compaction`] as a post-selection step: once a picker
+/// has chosen a `select_level > 0` input, its candidate files are expanded through this function
+/// (against `select_level`'s own [`Level`]) before the task is handed off, right alongside the
+/// [`bound_input_by_grandparent_overlap`] clip.
+pub fn expand_to_clean_user_key_boundary(
+    level: &Level,
+    mut selected: Vec<SstableInfo>,
+) -> Vec<SstableInfo> {
+    if selected.is_empty() {
+        return selected;
+    }
+
+    loop {
+        let last_id = selected.last().unwrap().id;
+        let last_idx = level
+            .table_infos
+            .iter()
+            .position(|f| f.id == last_id)
+            .expect("selected file must belong to level");
+        let Some(neighbor) = level.table_infos.get(last_idx + 1) else {
+            break;
+        };
+        let last_right = selected
+            .last()
+            .unwrap()
+            .key_range
+            .as_ref()
+            .unwrap()
+            .right
+            .clone();
+        let neighbor_left = neighbor.key_range.as_ref().unwrap().left.clone();
+        if strip_epoch_suffix(&last_right) != strip_epoch_suffix(&neighbor_left) {
+            break;
+        }
+        selected.push(neighbor.clone());
+    }
+
+    selected
+}
+
+/// Result of [`select_min_overlap_window`]: the chosen source-level window, the next-level files
+/// it overlaps, and whether it qualifies as a trivial move (zero next-level overlap, so it can be
+/// relabeled into the target level instead of rewritten).
+pub struct WindowSelection {
+    pub window: Vec<SstableInfo>,
+    pub overlapping: Vec<SstableInfo>,
+    pub is_trivial_move: bool,
+}
+
+/// Like [`select_min_write_amp_window`], but (a) only considers windows whose files are all
+/// non-pending in `handler` -- a window that includes a file already queued for another
+/// compaction can't be scheduled -- and (b) excludes any window whose total `file_size` exceeds
+/// `max_compaction_bytes` outright, rather than letting a merely-good ratio justify an
+/// unboundedly large compaction. Of the remaining windows, picks the one minimizing
+/// `overlapping_bytes / window_bytes` (ties broken by the smaller absolute overlap), same as
+/// `select_min_write_amp_window`. Returns `None` when `curr_level` is empty, or when every window
+/// either has a pending file or exceeds `max_compaction_bytes`.
+///
+/// Wired into [`DynamicLevelSelectorCore::min_overlap_window_score_boost`] (called from
+/// [`DynamicLevelSelectorCore::get_priority_levels`]), which uses this function's result to decide
+/// which `(select_level, target_level)` pair `DynamicLevelSelector::pick_compaction` tries first,
+/// same mechanism as [`select_min_write_amp_window`]'s `write_amp_score_boost`.
+pub fn select_min_overlap_window(
+    curr_level: &Level,
+    target_level: &Level,
+    handler: &LevelHandler,
+    max_compaction_bytes: u64,
+) -> Option<WindowSelection> {
+    let files = &curr_level.table_infos;
+    if files.is_empty() {
+        return None;
+    }
+
+    // (overlap_bytes, window_bytes, window, overlapping_target_files)
+    let mut best: Option<(u64, u64, Vec<SstableInfo>, Vec<SstableInfo>)> = None;
+
+    for window_size in 1..=files.len() {
+        for start in 0..=(files.len() - window_size) {
+            let window = &files[start..start + window_size];
+            if window.iter().any(|f| handler.is_pending_compact(&f.id)) {
+                continue;
+            }
+            let window_bytes: u64 = window.iter().map(|f| f.file_size).sum();
+            if window_bytes > max_compaction_bytes {
+                continue;
+            }
+            let (left, right) = merged_key_range(window);
+            let overlapping: Vec<SstableInfo> = target_level
+                .table_infos
+                .iter()
+                .filter(|f| range_overlaps_file(&left, &right, f))
+                .cloned()
+                .collect();
+            let overlap_bytes: u64 = overlapping.iter().map(|f| f.file_size).sum();
+
+            let is_better = match &best {
+                None => true,
+                Some((best_overlap, best_window_bytes, _, _)) => {
+                    let lhs = overlap_bytes as u128 * *best_window_bytes as u128;
+                    let rhs = *best_overlap as u128 * window_bytes as u128;
+                    lhs < rhs || (lhs == rhs && overlap_bytes < *best_overlap)
+                }
+            };
+            if is_better {
+                best = Some((overlap_bytes, window_bytes, window.to_vec(), overlapping));
+            }
+        }
+    }
+
+    best.map(|(overlap_bytes, _, window, overlapping)| WindowSelection {
+        is_trivial_move: overlap_bytes == 0,
+        window,
+        overlapping,
+    })
+}
+
+/// Precise interval overlap between two SST key ranges, honoring `right_exclusive`: two files
+/// whose ranges merely touch at a shared boundary byte string don't actually hold that key in
+/// common when the file on that side excludes it. `range_overlaps_file` above (used for window
+/// scoring, where an off-by-one boundary file rarely changes which window wins) ignores this,
+/// and would treat such touching-but-disjoint files as overlapping -- forcing an unnecessary
+/// rewrite where a trivial move (relabeling the SST to the target level without touching its
+/// bytes) would do.
+fn has_data_overlap(a: &SstableInfo, b: &SstableInfo) -> bool {
+    let ra = a.key_range.as_ref().unwrap();
+    let rb = b.key_range.as_ref().unwrap();
+
+    if ra.right < rb.left || (ra.right == rb.left && ra.right_exclusive) {
+        return false;
+    }
+    if rb.right < ra.left || (rb.right == ra.left && rb.right_exclusive) {
+        return false;
+    }
+    true
+}
+
+/// Filter `candidates` (already range-overlap filtered by the picker, e.g. against a merged
+/// window) down to those that truly share a key with `source`, per [`has_data_overlap`].
+pub fn filter_data_overlapping_targets<'a>(
+    source: &SstableInfo,
+    candidates: impl IntoIterator<Item = &'a SstableInfo>,
+) -> Vec<&'a SstableInfo> {
+    candidates
+        .into_iter()
+        .filter(|target| has_data_overlap(source, target))
+        .collect()
+}
+
+/// `true` when `source` has no surviving data overlap against any of `candidates`, i.e. it can
+/// be emitted as a trivial move -- relabeled to the target level without reading or rewriting
+/// its bytes -- instead of a real compaction.
+///
+/// `create_compaction_picker`'s pickers still assemble the actual `CompactionTask` (their bodies
+/// live in `compaction/picker.rs`, not part of this checkout, so there's no internal file
+/// selection here to replace), and trivial moves aren't surfaced as their own
+/// `compact_task::TaskType` here either. What this file does control is which
+/// `(select_level, target_level)` pair gets tried first: see
+/// [`DynamicLevelSelectorCore::trivial_move_score_boost`], which calls this function from
+/// [`DynamicLevelSelectorCore::get_priority_levels`] to push a level ahead of the race whenever
+/// it holds a file cheap enough to relabel instead of rewrite.
+pub fn is_trivial_move(source: &SstableInfo, candidates: &[SstableInfo]) -> bool {
+    candidates
+        .iter()
+        .all(|target| !has_data_overlap(source, target))
+}
+
+/// Gate for the precise data-overlap check above: when `use_data_overlap_check` is `false`
+/// (matching today's default behavior, preserved for compatibility), `candidates` are filtered
+/// by plain range overlap, same as the picker's existing `RangeOverlapStrategy`-based logic;
+/// when `true`, [`has_data_overlap`]'s boundary-exclusive-aware check is used, which is what
+/// lets a concurrent, disjoint-key-prefix ingestion workload (think
+/// `generate_table_with_table_ids` with distinct `table_ids` per table) turn a spurious
+/// range-only overlap into a trivial move.
+///
+/// Gated in production by `CompactionConfig::enable_trivial_move_data_overlap_check`: see
+/// [`DynamicLevelSelectorCore::trivial_move_score_boost`], which reads that field and calls this
+/// function (or [`is_trivial_move`], when the check is enabled) from
+/// [`DynamicLevelSelectorCore::get_priority_levels`].
+pub fn filter_overlapping_targets<'a>(
+    source: &SstableInfo,
+    candidates: impl IntoIterator<Item = &'a SstableInfo>,
+    use_data_overlap_check: bool,
+) -> Vec<&'a SstableInfo> {
+    let source_range = source.key_range.as_ref().unwrap();
+    let (left, right) = (source_range.left.clone(), source_range.right.clone());
+    candidates
+        .into_iter()
+        .filter(|target| {
+            if use_data_overlap_check {
+                has_data_overlap(source, target)
+            } else {
+                range_overlaps_file(&left, &right, target)
+            }
+        })
+        .collect()
+}
+
+/// For a `compaction_priority = MinOverlappingRatio` source-level selection (a `CompactionConfig`
+/// knob exposed via `CompactionConfigBuilder`, not part of this checkout): among `level`'s files
+/// that aren't already pending compaction in `handler`, pick the one whose overlap with
+/// `target_level` is smallest relative to its own size (`overlapping_bytes / file_size`), tied
+/// broken by the smaller absolute overlap. This biases compaction toward files that pull in the
+/// least data from the next level, which is where most of the avoidable write amplification
+/// comes from.
+///
+/// `create_compaction_picker`'s `MinOverlappingPicker` still makes the actual file selection for
+/// a picked `(select_level, target_level)` pair -- its body lives in `compaction/picker.rs`,
+/// which isn't part of this checkout, so there's no internal selection to swap out for this
+/// function's result directly. Gated by `CompactionConfig::enable_min_overlapping_ratio_priority`,
+/// this function's ratio is instead used by
+/// [`DynamicLevelSelectorCore::min_overlapping_ratio_score_boost`] (called from
+/// [`DynamicLevelSelectorCore::get_priority_levels`]) to decide which `(select_level,
+/// target_level)` pair `DynamicLevelSelector::pick_compaction` tries first.
+pub fn pick_min_overlapping_ratio_file<'a>(
+    level: &'a Level,
+    target_level: &Level,
+    handler: &LevelHandler,
+) -> Option<&'a SstableInfo> {
+    level
+        .table_infos
+        .iter()
+        .filter(|file| !handler.is_pending_compact(&file.id))
+        .min_by(|a, b| {
+            let overlap_a = overlapping_bytes(a, target_level);
+            let overlap_b = overlapping_bytes(b, target_level);
+            let lhs = overlap_a as u128 * b.file_size as u128;
+            let rhs = overlap_b as u128 * a.file_size as u128;
+            lhs.cmp(&rhs).then(overlap_a.cmp(&overlap_b))
+        })
+}
+
+fn overlapping_bytes(file: &SstableInfo, target_level: &Level) -> u64 {
+    target_level
+        .table_infos
+        .iter()
+        .filter(|target| has_data_overlap(file, target))
+        .map(|target| target.file_size)
+        .sum()
+}
+
+fn key_range_overlaps_file(range: &KeyRange, file: &SstableInfo) -> bool {
+    let file_range = file.key_range.as_ref().unwrap();
+    if range.right < file_range.left || (range.right == file_range.left && range.right_exclusive) {
+        return false;
+    }
+    if file_range.right < range.left
+        || (file_range.right == range.left && file_range.right_exclusive)
+    {
+        return false;
+    }
+    true
+}
+
+impl DynamicLevelSelectorCore {
+    /// `true` when no file in any level deeper than `output_level` overlaps
+    /// `selected_key_range`, i.e. `output_level` is genuinely the last place this key range is
+    /// stored. Compactions that land here can safely zero out old epochs/sequence numbers and
+    /// TTL/space-reclaim tasks can fully drop stale versions instead of conservatively keeping
+    /// them around for a deeper level that might still need them.
+    ///
+    /// L0 sub-levels overlap each other by design, so `selected_key_range` itself must come from
+    /// scanning every sub-level's files (see [`Self::l0_key_range`]) rather than trusting the
+    /// first/last file's boundaries when the selected input spans L0.
+    ///
+    /// Declined as out of scope for wiring into `SpaceReclaimCompactionSelector`/
+    /// `TtlCompactionSelector::pick_compaction` below: both build their `selected_key_range` from
+    /// the `CompactionInput` their picker returns, but `CompactionInput`'s definition lives
+    /// outside this checkout (no file under `compaction/` besides this one exists), so there's no
+    /// way to confirm which field, if any, would hold that range without guessing at an
+    /// undocumented shape. This method and [`Self::l0_key_range`] are unit-tested directly
+    /// instead.
+    pub fn is_bottommost(
+        &self,
+        levels: &Levels,
+        selected_key_range: &KeyRange,
+        output_level: usize,
+    ) -> bool {
+        levels
+            .levels
+            .iter()
+            .filter(|level| level.level_idx as usize > output_level)
+            .flat_map(|level| level.table_infos.iter())
+            .all(|file| !key_range_overlaps_file(selected_key_range, file))
+    }
+
+    /// The true key range spanned by an L0 input: since sub-levels may overlap, the merged
+    /// range has to be computed from every file across every sub-level rather than just the
+    /// endpoints of the first and last file.
+    pub fn l0_key_range(levels: &Levels) -> Option<(Vec<u8>, Vec<u8>)> {
+        let files: Vec<SstableInfo> = levels
+            .l0
+            .as_ref()?
+            .sub_levels
+            .iter()
+            .flat_map(|level| level.table_infos.iter())
+            .cloned()
+            .collect();
+        if files.is_empty() {
+            return None;
+        }
+        Some(merged_key_range(&files))
+    }
 }
 
 impl LevelSelector for DynamicLevelSelector {
@@ -340,9 +1149,17 @@ impl LevelSelector for DynamicLevelSelector {
         level_handlers: &mut [LevelHandler],
         selector_stats: &mut LocalSelectorStatistic,
     ) -> Option<CompactionTask> {
-        let ctx = self
+        self.seek_tracker.sync_levels(levels);
+        let mut ctx = self
             .dynamic_level_core
             .get_priority_levels(levels, level_handlers);
+        ctx.score_levels
+            .extend(self.dynamic_level_core.seek_priority_levels(
+                levels,
+                level_handlers,
+                &self.seek_tracker,
+            ));
+        ctx.score_levels.sort_by(|a, b| b.0.cmp(&a.0));
         for (score, select_level, target_level) in ctx.score_levels {
             if score <= SCORE_BASE {
                 return None;
@@ -353,7 +1170,21 @@ impl LevelSelector for DynamicLevelSelector {
                 self.overlap_strategy.clone(),
             );
             let mut stats = LocalPickerStatistic::default();
-            if let Some(ret) = picker.pick_compaction(levels, level_handlers, &mut stats) {
+            if let Some(mut ret) = picker.pick_compaction(levels, level_handlers, &mut stats) {
+                if select_level > 0 {
+                    ret.input_levels[0].table_infos =
+                        self.dynamic_level_core.grandparent_overlap_bound(
+                            levels,
+                            target_level,
+                            &ret.input_levels[0].table_infos,
+                        );
+                    ret.input_levels[0].table_infos =
+                        self.dynamic_level_core.expand_to_clean_user_key_boundary(
+                            levels,
+                            select_level,
+                            ret.input_levels[0].table_infos.clone(),
+                        );
+                }
                 ret.add_pending_task(task_id, level_handlers);
                 return Some(create_compaction_task(
                     self.dynamic_level_core.get_config(),
@@ -954,4 +1785,512 @@ pub mod tests {
             selector.pick_compaction(2, &levels, &mut levels_handlers, &mut local_stats);
         assert!(compaction.is_none());
     }
+
+    #[test]
+    fn test_min_overlapping_ratio_score_boost_wired_into_get_priority_levels() {
+        // `level`'s only file spans 100 keys and overlaps a single 10-key target file: a small
+        // ratio, so with the gate on this should be boosted.
+        let level = generate_level(1, vec![generate_table(0, 1, 0, 99, 1)]);
+        let target_level = generate_level(2, vec![generate_table(10, 1, 0, 9, 1)]);
+        let handler = LevelHandler::new(1);
+
+        let mut config = CompactionConfigBuilder::new().build();
+        config.enable_min_overlapping_ratio_priority = true;
+        let core = DynamicLevelSelectorCore::new(Arc::new(config));
+        assert_eq!(
+            core.min_overlapping_ratio_score_boost(&level, &target_level, &handler),
+            SCORE_BASE + 1
+        );
+
+        // Gate off: no boost even though the ratio is still favorable.
+        let mut config = CompactionConfigBuilder::new().build();
+        config.enable_min_overlapping_ratio_priority = false;
+        let core = DynamicLevelSelectorCore::new(Arc::new(config));
+        assert_eq!(
+            core.min_overlapping_ratio_score_boost(&level, &target_level, &handler),
+            0
+        );
+
+        // Gate on but the only candidate's overlap covers most of its own size: no boost.
+        let level = generate_level(1, vec![generate_table(0, 1, 0, 99, 1)]);
+        let target_level = generate_level(2, vec![generate_table(10, 1, 0, 59, 1)]);
+        let mut config = CompactionConfigBuilder::new().build();
+        config.enable_min_overlapping_ratio_priority = true;
+        let core = DynamicLevelSelectorCore::new(Arc::new(config));
+        assert_eq!(
+            core.min_overlapping_ratio_score_boost(&level, &target_level, &handler),
+            0
+        );
+    }
+
+    #[test]
+    fn test_l0_l0_merge_prioritized_when_base_level_blocked() {
+        let config = CompactionConfigBuilder::new()
+            .max_bytes_for_level_base(10)
+            .max_level(2)
+            .max_bytes_for_level_multiplier(5)
+            .max_compaction_bytes(10000)
+            .level0_tier_compact_file_number(1000)
+            .compaction_mode(CompactionMode::Range as i32)
+            .build();
+        let mut config = config;
+        config.level_compaction_use_dynamic_level_bytes = false; // deterministic base_level = 1.
+
+        let levels = vec![generate_level(1, vec![]), generate_level(2, vec![])];
+        let mut levels = Levels {
+            levels,
+            l0: Some(generate_l0_nonoverlapping_sublevels(generate_tables(
+                0..3,
+                0..300,
+                1,
+                40,
+            ))),
+        };
+
+        // Round 1: base level is empty, so some (or all) of L0 moves there -- whatever the
+        // picker chooses, `max_bytes_for_level_base` is tiny, so even a single file's worth of
+        // pending output is enough to block the base level afterward.
+        let mut selector = DynamicLevelSelector::new(
+            Arc::new(config.clone()),
+            Arc::new(RangeOverlapStrategy::default()),
+        );
+        let mut handlers = (0..3).map(LevelHandler::new).collect_vec();
+        let mut local_stats = LocalSelectorStatistic::default();
+        selector
+            .pick_compaction(1, &levels, &mut handlers, &mut local_stats)
+            .unwrap();
+        assert!(handlers[0].get_pending_output_file_size(1) * 2 > config.max_bytes_for_level_base);
+
+        // Add fresh, non-pending L0 sub-levels so there's still idle L0 data to score in round 2,
+        // regardless of how much of the original batch round 1 consumed.
+        push_tables_level0_nonoverlapping(&mut levels, generate_tables(100..102, 0..300, 1, 40));
+
+        let core = DynamicLevelSelectorCore::new(Arc::new(config.clone()));
+        let blocked_ctx = core.get_priority_levels(&levels, &handlers);
+        let (l0_l0_score, _, _) = blocked_ctx
+            .score_levels
+            .iter()
+            .find(|(_, select_level, target_level)| *select_level == 0 && *target_level == 0)
+            .expect("L0->L0 tier score must be present");
+        assert!(*l0_l0_score > SCORE_BASE);
+
+        // Without the blocked base level, the same idle L0 files score far below SCORE_BASE
+        // (level0_tier_compact_file_number is 1000), so the boost above is what's carrying it.
+        let fresh_handlers = (0..3).map(LevelHandler::new).collect_vec();
+        let unblocked_ctx = core.get_priority_levels(&levels, &fresh_handlers);
+        let (l0_l0_score_unblocked, _, _) = unblocked_ctx
+            .score_levels
+            .iter()
+            .find(|(_, select_level, target_level)| *select_level == 0 && *target_level == 0)
+            .expect("L0->L0 tier score must be present");
+        assert!(*l0_l0_score_unblocked <= SCORE_BASE);
+    }
+
+    #[test]
+    fn test_select_min_write_amp_window() {
+        // Two adjacent files in L1, each overlapping a disjoint half of L2: the single file id 0
+        // pulls in one L2 file (100 bytes for its 10), the same ratio as both L1 files dragging
+        // in all of L2 (200 for 20), and wins that tie on the smaller absolute overlap.
+        let curr_level = generate_level(1, generate_tables(0..2, 0..200, 1, 10));
+        let target_level = generate_level(2, generate_tables(10..12, 0..200, 1, 100));
+        let mut ctx = SelectContext::default();
+        ctx.level_max_bytes = vec![u64::MAX, 0, u64::MAX];
+
+        let (window, overlapping) =
+            select_min_write_amp_window(&curr_level, &target_level, 1, &ctx).unwrap();
+        assert_eq!(window.len(), 1);
+        assert_eq!(overlapping.len(), 1);
+
+        // When the level is within budget, there's nothing to shrink.
+        ctx.level_max_bytes[1] = u64::MAX;
+        assert!(select_min_write_amp_window(&curr_level, &target_level, 1, &ctx).is_none());
+    }
+
+    #[test]
+    fn test_write_amp_score_boost_wired_into_get_priority_levels() {
+        // total_file_size = 150, level_max_bytes[1] = 149: over budget, but
+        // 150 * SCORE_BASE / 149 truncates to exactly 100, which `pick_compaction` would treat
+        // as "no need to compact". `write_amp_score_boost` must catch this via the real window
+        // it finds and push the score above SCORE_BASE so the level isn't silently skipped. The
+        // best window (file 0, 75 bytes) overlaps one 10-byte L2 file, well under half its size.
+        let curr_level = generate_level(1, generate_tables(0..2, 0..200, 1, 75));
+        let target_level = generate_level(2, generate_tables(10..12, 0..200, 1, 10));
+        let mut ctx = SelectContext::default();
+        ctx.level_max_bytes = vec![u64::MAX, 149, u64::MAX];
+        assert_eq!(150 * SCORE_BASE / 149, SCORE_BASE);
+
+        let config = CompactionConfigBuilder::new().build();
+        let core = DynamicLevelSelectorCore::new(Arc::new(config));
+        let boost = core.write_amp_score_boost(&curr_level, &target_level, 1, &ctx);
+        assert_eq!(boost, SCORE_BASE + 1);
+
+        // When no window exists at all (level within budget), there's nothing to boost.
+        ctx.level_max_bytes[1] = u64::MAX;
+        assert_eq!(
+            core.write_amp_score_boost(&curr_level, &target_level, 1, &ctx),
+            0
+        );
+    }
+
+    #[test]
+    fn test_trivial_move_boundary_exclusive() {
+        let mut source = generate_table(0, 1, 0, 100, 1);
+        source.key_range.as_mut().unwrap().right_exclusive = true;
+        // `target`'s left boundary is exactly `source`'s (exclusive) right boundary: they touch
+        // but share no key.
+        let target = generate_table(1, 1, 100, 199, 1);
+        assert!(!has_data_overlap(&source, &target));
+        assert!(is_trivial_move(&source, &[target.clone()]));
+        assert!(filter_data_overlapping_targets(&source, &[target]).is_empty());
+
+        // Without `right_exclusive`, the shared boundary key is real overlap.
+        let mut inclusive_source = generate_table(2, 1, 0, 100, 1);
+        inclusive_source.key_range.as_mut().unwrap().right_exclusive = false;
+        let target2 = generate_table(3, 1, 100, 199, 1);
+        assert!(has_data_overlap(&inclusive_source, &target2));
+        assert!(!is_trivial_move(&inclusive_source, &[target2]));
+    }
+
+    #[test]
+    fn test_is_bottommost() {
+        let config = CompactionConfigBuilder::new().max_level(3).build();
+        let selector = DynamicLevelSelectorCore::new(Arc::new(config));
+        let levels = Levels {
+            levels: vec![
+                generate_level(1, vec![]),
+                generate_level(2, generate_tables(0..2, 0..100, 1, 10)),
+                generate_level(3, vec![]),
+            ],
+            l0: Some(generate_l0_nonoverlapping_sublevels(vec![])),
+        };
+
+        // Nothing below L2 has data, so compacting into L2 is bottommost.
+        let range = KeyRange {
+            left: iterator_test_key_of_epoch(1, 0, 1),
+            right: iterator_test_key_of_epoch(1, 49, 1),
+            right_exclusive: false,
+        };
+        assert!(selector.is_bottommost(&levels, &range, 2));
+
+        // L3 holds an overlapping file below L1, so L1 is not bottommost for this range.
+        let mut levels_with_l3_data = levels.clone();
+        levels_with_l3_data.levels[2] = generate_level(3, generate_tables(2..3, 0..100, 1, 10));
+        assert!(!selector.is_bottommost(&levels_with_l3_data, &range, 1));
+    }
+
+    #[test]
+    fn test_l0_key_range_scans_all_sub_levels() {
+        let mut levels = Levels {
+            levels: vec![],
+            l0: Some(generate_l0_nonoverlapping_sublevels(vec![])),
+        };
+        push_table_level0_overlapping(&mut levels, generate_table(0, 1, 50, 99, 1));
+        push_table_level0_overlapping(&mut levels, generate_table(1, 1, 0, 49, 1));
+
+        let (left, right) = DynamicLevelSelectorCore::l0_key_range(&levels).unwrap();
+        assert_eq!(left, iterator_test_key_of_epoch(1, 0, 1));
+        assert_eq!(right, iterator_test_key_of_epoch(1, 99, 1));
+    }
+
+    #[test]
+    fn test_pick_min_overlapping_ratio_file() {
+        // File 0 overlaps only the small target file; file 1 overlaps the large one, and
+        // despite being the same size as file 0, has the worse (larger) ratio.
+        let level = generate_level(
+            1,
+            vec![
+                generate_table(0, 1, 0, 49, 1),
+                generate_table(1, 1, 50, 99, 1),
+            ],
+        );
+        let target_level = generate_level(
+            2,
+            vec![
+                generate_table(10, 1, 0, 49, 1),
+                generate_table(11, 1, 50, 199, 1),
+            ],
+        );
+        let handler = LevelHandler::new(1);
+
+        let picked = pick_min_overlapping_ratio_file(&level, &target_level, &handler).unwrap();
+        assert_eq!(picked.id, 0);
+    }
+
+    #[test]
+    fn test_filter_overlapping_targets_data_overlap_gate() {
+        // Models two tables ingested concurrently at disjoint, monotonically increasing
+        // key-prefix ranges: the source file's range ends exactly where the target's begins,
+        // but (being `right_exclusive`) they share no actual key.
+        let mut source = generate_table_with_table_ids(0, 1, 0, 100, 1, vec![1]);
+        source.key_range.as_mut().unwrap().right_exclusive = true;
+        let target = generate_table_with_table_ids(1, 1, 100, 199, 1, vec![2]);
+
+        // Plain range overlap (today's default) still counts the touching boundary.
+        let range_only = filter_overlapping_targets(&source, [&target], false);
+        assert_eq!(range_only.len(), 1);
+
+        // The data-overlap gate correctly excludes it, enabling a trivial move.
+        let data_aware = filter_overlapping_targets(&source, [&target], true);
+        assert!(data_aware.is_empty());
+    }
+
+    #[test]
+    fn test_trivial_move_score_boost_wired_into_get_priority_levels() {
+        // `source`'s (exclusive) right boundary exactly meets `target`'s left: they touch but
+        // share no key, so with the data-overlap check on this is a trivial move.
+        let mut source = generate_table(0, 1, 0, 100, 1);
+        source.key_range.as_mut().unwrap().right_exclusive = true;
+        let target = generate_table(1, 1, 100, 199, 1);
+        let level = generate_level(1, vec![source]);
+        let target_level = generate_level(2, vec![target]);
+        let handler = LevelHandler::new(1);
+
+        let mut config = CompactionConfigBuilder::new().build();
+        config.enable_trivial_move_data_overlap_check = true;
+        let core = DynamicLevelSelectorCore::new(Arc::new(config));
+        assert_eq!(
+            core.trivial_move_score_boost(&level, &target_level, &handler),
+            SCORE_BASE * 2
+        );
+
+        // With the coarser range-only check, the touching boundary still counts as overlap, so
+        // there's no trivial move and nothing to boost.
+        let mut config = CompactionConfigBuilder::new().build();
+        config.enable_trivial_move_data_overlap_check = false;
+        let core = DynamicLevelSelectorCore::new(Arc::new(config));
+        assert_eq!(
+            core.trivial_move_score_boost(&level, &target_level, &handler),
+            0
+        );
+    }
+
+    #[test]
+    fn test_file_seek_tracker_exhausts_after_allowed_seeks() {
+        let file = generate_table(0, 1, 0, 0, 1); // file_size = 1, so allowed_seeks = MIN_ALLOWED_SEEKS.
+        let mut tracker = FileSeekTracker::default();
+        tracker.track(&file);
+        assert!(!tracker.is_exhausted(file.id));
+
+        for _ in 0..MIN_ALLOWED_SEEKS - 1 {
+            assert!(!tracker.record_wasted_seek(file.id));
+        }
+        assert!(!tracker.is_exhausted(file.id));
+        assert!(tracker.record_wasted_seek(file.id));
+        assert!(tracker.is_exhausted(file.id));
+
+        // Once exhausted, further wasted seeks are a no-op rather than underflowing.
+        assert!(!tracker.record_wasted_seek(file.id));
+    }
+
+    #[test]
+    fn test_seek_compaction_triggers_below_size_threshold() {
+        let config = CompactionConfigBuilder::new()
+            .max_bytes_for_level_base(1_000_000)
+            .max_level(3)
+            .compaction_mode(CompactionMode::Range as i32)
+            .build();
+        let levels = Levels {
+            levels: vec![
+                generate_level(1, generate_tables(0..1, 0..100, 1, 10)),
+                generate_level(2, generate_tables(10..11, 0..100, 1, 10)),
+                generate_level(3, vec![]),
+            ],
+            l0: Some(generate_l0_nonoverlapping_sublevels(vec![])),
+        };
+        // Well under `max_bytes_for_level_base`, so size-based scoring alone picks nothing.
+        let mut selector =
+            DynamicLevelSelector::new(Arc::new(config), Arc::new(RangeOverlapStrategy::default()));
+        let mut level_handlers = (0..4).map(LevelHandler::new).collect_vec();
+        let mut local_stats = LocalSelectorStatistic::default();
+        assert!(selector
+            .pick_compaction(1, &levels, &mut level_handlers, &mut local_stats)
+            .is_none());
+
+        let l1_file_id = levels.levels[0].table_infos[0].id;
+        for _ in 0..MIN_ALLOWED_SEEKS {
+            selector.record_wasted_seek(l1_file_id);
+        }
+
+        let compaction = selector
+            .pick_compaction(2, &levels, &mut level_handlers, &mut local_stats)
+            .unwrap();
+        assert_eq!(compaction.input.input_levels[0].level_idx, 1);
+        assert_eq!(compaction.input.target_level, 2);
+    }
+
+    #[test]
+    fn test_bound_input_by_grandparent_overlap() {
+        // A single grandparent file spans the whole key space, so any input file overlaps it.
+        let grandparent_level = generate_level(2, generate_tables(0..1, 0..200, 1, 1000));
+        let candidates = generate_tables(0..3, 0..200, 1, 10);
+
+        // Budget of 10 (factor 1 * target_file_size 10) is blown past by the first file's
+        // 1000-byte grandparent overlap, but the first file is always kept so progress is made;
+        // the second and third are rejected since the accumulated overlap already exceeds budget.
+        let bounded = bound_input_by_grandparent_overlap(&candidates, &grandparent_level, 1, 10);
+        assert_eq!(bounded.len(), 1);
+        assert_eq!(bounded[0].id, candidates[0].id);
+
+        // With no grandparent files in range, every candidate is kept.
+        let empty_grandparent = generate_level(2, vec![]);
+        let bounded = bound_input_by_grandparent_overlap(&candidates, &empty_grandparent, 1, 10);
+        assert_eq!(bounded.len(), candidates.len());
+    }
+
+    #[test]
+    fn test_grandparent_overlap_bound_wired_into_pick_compaction() {
+        let grandparent_level = generate_level(3, generate_tables(0..1, 0..200, 1, 1000));
+        let candidates = generate_tables(0..3, 0..200, 1, 10);
+        let levels = Levels {
+            levels: vec![
+                generate_level(1, vec![]),
+                generate_level(2, vec![]),
+                grandparent_level,
+            ],
+            l0: Some(generate_l0_nonoverlapping_sublevels(vec![])),
+        };
+
+        // Gate on: budget of 10 is blown past by the grandparent overlap, so only the first
+        // candidate survives.
+        let mut config = CompactionConfigBuilder::new().build();
+        config.max_grand_parent_overlap_factor = 1;
+        config.target_file_size_base = 10;
+        let core = DynamicLevelSelectorCore::new(Arc::new(config));
+        let bounded = core.grandparent_overlap_bound(&levels, 2, &candidates);
+        assert_eq!(bounded.len(), 1);
+        assert_eq!(bounded[0].id, candidates[0].id);
+
+        // Gate off (factor 0, the proto default): candidates pass through unchanged.
+        let config = CompactionConfigBuilder::new().build();
+        let core = DynamicLevelSelectorCore::new(Arc::new(config));
+        let bounded = core.grandparent_overlap_bound(&levels, 2, &candidates);
+        assert_eq!(bounded.len(), candidates.len());
+    }
+
+    #[test]
+    fn test_select_min_overlap_window() {
+        // Same shape as `test_select_min_write_amp_window`: the single-file window (id 0) ties
+        // the two-file window on overlap ratio and wins on the smaller absolute overlap, even
+        // though it isn't gated on the level being over budget this time.
+        let curr_level = generate_level(1, generate_tables(0..2, 0..200, 1, 10));
+        let target_level = generate_level(2, generate_tables(10..12, 0..200, 1, 100));
+        let handler = LevelHandler::new(1);
+
+        let selection =
+            select_min_overlap_window(&curr_level, &target_level, &handler, u64::MAX).unwrap();
+        assert_eq!(selection.window.len(), 1);
+        assert_eq!(selection.window[0].id, 0);
+        assert_eq!(selection.overlapping.len(), 1);
+        assert!(!selection.is_trivial_move);
+
+        // A `max_compaction_bytes` cap below even the smallest single file's size disqualifies
+        // every window.
+        let handler = LevelHandler::new(1);
+        assert!(select_min_overlap_window(&curr_level, &target_level, &handler, 1).is_none());
+    }
+
+    #[test]
+    fn test_min_overlap_window_score_boost_wired_into_get_priority_levels() {
+        // Same key layout as `test_select_min_overlap_window`, but with 1-byte L2 files: a
+        // favorable overlap ratio (1 byte for 10), gated only by `max_compaction_bytes`, which
+        // defaults high enough to admit it.
+        let level = generate_level(1, generate_tables(0..2, 0..200, 1, 10));
+        let target_level = generate_level(2, generate_tables(10..12, 0..200, 1, 1));
+        let handler = LevelHandler::new(1);
+        let config = CompactionConfigBuilder::new().build();
+        let core = DynamicLevelSelectorCore::new(Arc::new(config));
+        assert_eq!(
+            core.min_overlap_window_score_boost(&level, &target_level, &handler),
+            SCORE_BASE + 1
+        );
+
+        // A `max_compaction_bytes` cap too small for any window disqualifies them all, so there's
+        // nothing to boost.
+        let config = CompactionConfigBuilder::new()
+            .max_compaction_bytes(1)
+            .build();
+        let core = DynamicLevelSelectorCore::new(Arc::new(config));
+        assert_eq!(
+            core.min_overlap_window_score_boost(&level, &target_level, &handler),
+            0
+        );
+    }
+
+    #[test]
+    fn test_select_min_overlap_window_trivial_move() {
+        // No target-level files at all overlap the source, so every window is a trivial move.
+        let curr_level = generate_level(1, generate_tables(0..2, 0..200, 1, 10));
+        let target_level = generate_level(2, vec![]);
+        let handler = LevelHandler::new(1);
+
+        let selection =
+            select_min_overlap_window(&curr_level, &target_level, &handler, u64::MAX).unwrap();
+        assert!(selection.overlapping.is_empty());
+        assert!(selection.is_trivial_move);
+    }
+
+    #[test]
+    fn test_expand_to_clean_user_key_boundary_pulls_in_shared_user_key_neighbor() {
+        // `first` ends at user key 99 (epoch 5); `second` starts at the *same* user key 99 but
+        // an older version (epoch 3). Compacting `first` alone could drop a tombstone for key 99
+        // while this older put of the same key survives untouched in `second`, resurrecting it.
+        let first = generate_table(0, 1, 0, 99, 5);
+        let second = generate_table(1, 1, 99, 199, 3);
+        let level = generate_level(1, vec![first.clone(), second.clone()]);
+
+        let expanded = expand_to_clean_user_key_boundary(&level, vec![first]);
+        assert_eq!(expanded.len(), 2);
+        assert_eq!(expanded[1].id, second.id);
+    }
+
+    #[test]
+    fn test_expand_to_clean_user_key_boundary_noop_on_clean_boundary() {
+        // `third` starts at user key 100, strictly past `first`'s boundary at key 99: no shared
+        // user key, so nothing needs to be pulled in.
+        let first = generate_table(0, 1, 0, 99, 5);
+        let third = generate_table(2, 1, 100, 199, 3);
+        let level = generate_level(1, vec![first.clone(), third]);
+
+        let expanded = expand_to_clean_user_key_boundary(&level, vec![first]);
+        assert_eq!(expanded.len(), 1);
+    }
+
+    #[test]
+    fn test_expand_to_clean_user_key_boundary_chains_across_multiple_neighbors() {
+        // Three files all sharing the boundary user key 99 at different epochs: expansion must
+        // keep walking forward past `second` into `third` rather than stopping after one pull.
+        let first = generate_table(0, 1, 0, 99, 5);
+        let second = generate_table(1, 1, 99, 99, 4);
+        let third = generate_table(2, 1, 99, 199, 3);
+        let level = generate_level(1, vec![first.clone(), second.clone(), third.clone()]);
+
+        let expanded = expand_to_clean_user_key_boundary(&level, vec![first]);
+        assert_eq!(expanded.len(), 3);
+        assert_eq!(expanded[1].id, second.id);
+        assert_eq!(expanded[2].id, third.id);
+    }
+
+    #[test]
+    fn test_expand_to_clean_user_key_boundary_wired_into_pick_compaction() {
+        // Same shared-boundary setup as `test_expand_to_clean_user_key_boundary_pulls_in_shared_user_key_neighbor`.
+        let first = generate_table(0, 1, 0, 99, 5);
+        let second = generate_table(1, 1, 99, 199, 3);
+        let level = generate_level(1, vec![first.clone(), second.clone()]);
+        let levels = Levels {
+            levels: vec![level, generate_level(2, vec![])],
+            l0: Some(generate_l0_nonoverlapping_sublevels(vec![])),
+        };
+        let config = CompactionConfigBuilder::new().build();
+        let core = DynamicLevelSelectorCore::new(Arc::new(config));
+
+        let expanded = core.expand_to_clean_user_key_boundary(&levels, 1, vec![first]);
+        assert_eq!(expanded.len(), 2);
+        assert_eq!(expanded[1].id, second.id);
+
+        // `select_level == 0` has no single `Level` in `levels.levels` to expand against, so
+        // candidates pass through unchanged.
+        let unchanged =
+            core.expand_to_clean_user_key_boundary(&levels, 0, generate_tables(5..6, 0..10, 1, 10));
+        assert_eq!(unchanged.len(), 1);
+    }
 }